@@ -0,0 +1,71 @@
+#!/usr/bin/env python3
+"""Generates src/unicode_tables.rs from the Unicode Character Database bundled with Python.
+
+The tables are built from UCD 14.0.0, which is what Python 3.11 ships, so run it with that version:
+
+    python3.11 scripts/unicode_tables.py > src/unicode_tables.rs
+"""
+
+import sys
+import unicodedata as ucd
+
+UCD_VERSION = "14.0.0"
+
+# Hangul syllables are decomposed and composed arithmetically
+HANGUL_SYLLABLES = range(0xAC00, 0xAC00 + 11172)
+
+
+def char(cp):
+    return "'\\u{%X}'" % cp
+
+
+def main():
+    if ucd.unidata_version != UCD_VERSION:
+        sys.exit("needs the UCD %s, this Python has %s" % (UCD_VERSION, ucd.unidata_version))
+
+    out = [
+        "// Canonical decompositions and combining classes for Unicode normalisation, generated by",
+        "// scripts/unicode_tables.py from the Unicode Character Database %s. Hangul syllables are left" % UCD_VERSION,
+        "// out, as they are decomposed and composed arithmetically.",
+        "",
+        "/// Each character with a canonical decomposition, sorted, with the one or two characters it",
+        "/// decomposes into (`'\\0'` when there is only one) and whether those compose back into it in NFC.",
+        "pub(crate) const DECOMPOSITIONS: &[(char, char, char, bool)] = &[",
+    ]
+    for cp in range(0x110000):
+        if cp in HANGUL_SYLLABLES:
+            continue
+        decomposition = ucd.decomposition(chr(cp))
+        # Compatibility decompositions start with a tag like <font>
+        if not decomposition or decomposition.startswith("<"):
+            continue
+        parts = [int(part, 16) for part in decomposition.split()]
+        assert len(parts) in (1, 2)
+        composes = len(parts) == 2 and ucd.normalize("NFC", chr(cp)) == chr(cp)
+        second = char(parts[1]) if len(parts) == 2 else "'\\0'"
+        out.append("    (%s, %s, %s, %s)," % (char(cp), char(parts[0]), second, "true" if composes else "false"))
+    out.append("];")
+
+    out += [
+        "",
+        "/// Ranges of characters with a non-zero canonical combining class, sorted, with the class.",
+        "pub(crate) const COMBINING_CLASSES: &[(char, char, u8)] = &[",
+    ]
+    ranges = []
+    for cp in range(0x110000):
+        combining_class = ucd.combining(chr(cp))
+        if not combining_class:
+            continue
+        if ranges and ranges[-1][1] == cp - 1 and ranges[-1][2] == combining_class:
+            ranges[-1][1] = cp
+        else:
+            ranges.append([cp, cp, combining_class])
+    for start, end, combining_class in ranges:
+        out.append("    (%s, %s, %d)," % (char(start), char(end), combining_class))
+    out.append("];")
+
+    print("\n".join(out))
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,66 @@
+// Azure Pipelines `trigger.paths` and `pr.paths` filters. Paths are prefixes of the files they
+// cover, the order of the filters doesn't matter, and the most specific matching filter decides,
+// with excludes winning ties.
+// See https://learn.microsoft.com/en-us/azure/devops/pipelines/repos/azure-repos-git#paths
+
+use crate::wildcard::{parse, split_segments, Syntax};
+use crate::{Dialect, Glob, Pattern, Segment};
+
+const AZURE_SYNTAX: Syntax =
+    Syntax { classes: false, escapes: false, braces: false, brace_ranges: false, extglobs: false };
+
+// Parses a pattern for Dialect::Azure: anchored at the root, with a leading / being optional, and
+// covering everything below the path it names
+pub(crate) fn parse_pattern(pattern: &str) -> Vec<(Pattern, bool)> {
+    let body = pattern.trim_start_matches('/');
+    let offset = pattern.len() - body.len();
+    let body = body.trim_end_matches('/');
+
+    let mut segments = if body.is_empty() { vec![] } else { split_segments(&parse(body, offset, AZURE_SYNTAX)) };
+    segments.push(Segment::DoubleStar);
+
+    vec![(Pattern { segments }, false)]
+}
+
+/// The `include` and `exclude` lists of an Azure Pipelines path filter.
+#[derive(Debug, Clone)]
+pub struct AzurePathFilter {
+    include: Vec<Glob>,
+    exclude: Vec<Glob>,
+}
+
+impl AzurePathFilter {
+    pub fn new(include: &[&str], exclude: &[&str]) -> AzurePathFilter {
+        let globs =
+            |patterns: &[&str]| patterns.iter().map(|pattern| Glob::with_dialect(pattern, Dialect::Azure)).collect();
+        AzurePathFilter { include: globs(include), exclude: globs(exclude) }
+    }
+
+    pub fn include(&self) -> &[Glob] {
+        &self.include
+    }
+
+    pub fn exclude(&self) -> &[Glob] {
+        &self.exclude
+    }
+
+    /// Whether a change to the path triggers the pipeline.
+    ///
+    /// Without includes the whole repository is included. A path excluded by one filter can still
+    /// be included by a deeper one, e.g. `tools/trigger` within an excluded `tools`.
+    pub fn is_triggered_by(&self, path: &str) -> bool {
+        let deepest = |globs: &[Glob]| globs.iter().filter(|glob| glob.is_match(path)).map(specificity).max();
+
+        let included = if self.include.is_empty() { Some(0) } else { deepest(&self.include) };
+        match (included, deepest(&self.exclude)) {
+            (None, _) => false,
+            (Some(_), None) => true,
+            (Some(included), Some(excluded)) => included > excluded,
+        }
+    }
+}
+
+// The number of segments the filter names, not counting the implied ** for everything below it
+fn specificity(glob: &Glob) -> usize {
+    glob.variants.iter().map(|variant| variant.segments.len() - 1).max().unwrap_or(0)
+}
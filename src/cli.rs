@@ -0,0 +1,1032 @@
+// The `pathglob` command line tool. It lives in the library so that it can share the crate's
+// internals and be tested without spawning a process.
+
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use crate::json::Json;
+use crate::workflow::PATH_EVENTS;
+use crate::yaml;
+use crate::{
+    apply_fixes, apply_quoting, diff_workflows, find_dead_patterns, find_files_bytes, find_fixes,
+    find_quoting_problems, find_shadowed_patterns, find_uncovered_files, parse_workflow, read_git_index_bytes, run_lsp,
+    run_repl, trigger_matrix, Dialect, Filter, Fix, Glob, Normalization, PatternFix, QuotingProblem, SourcePattern,
+    Workflow,
+};
+
+/// The version of the `--format json` output. It changes whenever a field is removed or changes
+/// meaning; new fields may be added without a new version.
+pub const JSON_SCHEMA_VERSION: usize = 1;
+
+const USAGE: &str = "\
+Usage: pathglob <command> [options]
+
+Commands:
+  match [--dialect <name>] [--normalize | --strict-paths] <pattern>... [-- <path>...]
+      Check which paths the ordered list of patterns includes. Paths are read from standard
+      input, one per line, when none are given. With --normalize, paths like ./src//a.rs or
+      src\\a.rs are matched as src/a.rs; with --strict-paths, they are reported as errors.
+  explain [--dialect <name>] [--normalize | --strict-paths] <pattern>... [-- <path>...]
+      Like match, showing how each pattern treats each path.
+  which-workflows [--repo <dir>] [--branch <name>] <path>...
+      List the workflow events a change to the paths runs, and the patterns that decided.
+  lint [--repo <dir>] [--fix] [<workflow>...]
+      Report workflow path filters that match none of the repository's tracked files or have no
+      effect where they are, and patterns with common mistakes or missing quotes. With --fix, the
+      mistakes and quotes are rewritten in place.
+  coverage [--repo <dir>] [--branch <name>] [<workflow>...]
+      List the tracked files that trigger no workflow when pushed to the branch (default: main),
+      grouped by directory.
+  matrix [--repo <dir>] [--depth <n>] [<workflow>...]
+      Show which workflows each directory and file triggers, down to the given depth.
+  diff [--repo <dir>] <old workflow> <new workflow>
+      List the tracked files that start or stop triggering each event between two versions of a
+      workflow.
+  repl [--repo <dir>] [--dialect <name>]
+      Try patterns against the repository's tracked files: each line typed adds a pattern, and
+      the files the list includes are shown along with the near misses.
+  lsp
+      Run a language server on standard input and output, which checks the filters of workflow
+      files as they are edited, shows the files a path pattern matches on hover and offers fixes.
+
+Options:
+  --format text|json    Output format of every command but repl and lsp, which only take text; matrix
+                        also takes csv and markdown, and lint github (GitHub Actions annotations)
+                        and sarif.
+  --dialect <name>      github (default), picomatch, picomatch-dot, codeowners, gitignore,
+                        gitlab or azure.
+
+Workflows default to .github/workflows/*.yml in the repository.
+";
+
+/// Runs the command line tool with the arguments that follow the program name, writing results to
+/// `out` and errors to standard error.
+pub fn run_cli(args: &[String], out: &mut dyn Write) -> ExitCode {
+    let rest = args.get(1..).unwrap_or_default();
+    let result = match args.first().map(|command| command.as_str()) {
+        Some("match") => match_paths(rest, out, false),
+        Some("explain") => match_paths(rest, out, true),
+        Some("which-workflows") => which_workflows(rest, out),
+        Some("lint") => lint(rest, out),
+        Some("coverage") => coverage(rest, out),
+        Some("matrix") => matrix(rest, out),
+        Some("diff") => diff(rest, out),
+        Some("repl") => repl(rest, out),
+        Some("lsp") => lsp(rest, out),
+        Some("-h" | "--help") => write!(out, "{}", USAGE).map(|_| ExitCode::SUCCESS),
+        _ => {
+            eprint!("{}", USAGE);
+            Ok(ExitCode::from(2))
+        }
+    };
+
+    result.unwrap_or_else(|error| {
+        eprintln!("pathglob: {}", error);
+        ExitCode::from(2)
+    })
+}
+
+// The options of every command; each one only looks at those it documents
+struct Args {
+    repo: PathBuf,
+    branch: Option<String>,
+    depth: Option<usize>,
+    format: String,
+    dialect: Dialect,
+    normalization: Normalization,
+    fix: bool,
+    positionals: Vec<String>,
+    // What follows `--`, if it was given
+    after_separator: Option<Vec<String>>,
+}
+
+fn parse_args(args: &[String]) -> io::Result<Args> {
+    let mut parsed = Args {
+        repo: PathBuf::from("."),
+        branch: None,
+        depth: None,
+        format: "text".to_string(),
+        dialect: Dialect::GitHub,
+        normalization: Normalization::Off,
+        fix: false,
+        positionals: vec![],
+        after_separator: None,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value =
+            |option: &str| args.next().cloned().ok_or_else(|| usage_error(&format!("{} needs a value", option)));
+        match arg.as_str() {
+            "--repo" => parsed.repo = PathBuf::from(value(arg)?),
+            "--branch" => parsed.branch = Some(value(arg)?),
+            "--depth" => {
+                let depth = value(arg)?;
+                parsed.depth = Some(depth.parse().map_err(|_| usage_error(&format!("invalid depth {}", depth)))?);
+            }
+            "--format" => parsed.format = value(arg)?,
+            "--dialect" => parsed.dialect = parse_dialect(&value(arg)?)?,
+            "--fix" => parsed.fix = true,
+            "--normalize" => parsed.normalization = Normalization::Normalize,
+            "--strict-paths" => parsed.normalization = Normalization::Strict,
+            "--" => {
+                parsed.after_separator = Some(args.cloned().collect());
+                break;
+            }
+            option if option.starts_with("--") => return Err(usage_error(&format!("unknown option {}", option))),
+            positional => parsed.positionals.push(positional.to_string()),
+        }
+    }
+
+    Ok(parsed)
+}
+
+fn parse_dialect(name: &str) -> io::Result<Dialect> {
+    Ok(match name {
+        "github" => Dialect::GitHub,
+        "picomatch" => Dialect::Picomatch { dot: false },
+        "picomatch-dot" => Dialect::Picomatch { dot: true },
+        "codeowners" => Dialect::Codeowners,
+        "gitignore" => Dialect::Gitignore,
+        "gitlab" => Dialect::GitLab,
+        "azure" => Dialect::Azure,
+        _ => return Err(usage_error(&format!("unknown dialect {}", name))),
+    })
+}
+
+fn usage_error(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message.to_string())
+}
+
+fn check_format(format: &str, supported: &[&str]) -> io::Result<()> {
+    if supported.contains(&format) {
+        Ok(())
+    } else {
+        Err(usage_error(&format!("unknown format {}", format)))
+    }
+}
+
+// Every JSON document starts with the schema version and the command that produced it
+fn json_document(command: &str, body: Json) -> Json {
+    let mut entries =
+        vec![("version".to_string(), JSON_SCHEMA_VERSION.into()), ("command".to_string(), command.into())];
+    if let Json::Object(fields) = body {
+        entries.extend(fields);
+    }
+    Json::Object(entries)
+}
+
+impl Args {
+    // The workflow files given on the command line, or those of the repository
+    fn workflows(&self) -> io::Result<Vec<PathBuf>> {
+        if self.positionals.is_empty() {
+            repo_workflows(&self.repo)
+        } else {
+            Ok(self.positionals.iter().map(PathBuf::from).collect())
+        }
+    }
+
+    fn repo_files(&self) -> io::Result<Vec<Vec<u8>>> {
+        tracked_files(&self.repo)
+    }
+}
+
+// The tracked files from the git index, or every file on disk outside of a git repository. Paths are
+// kept as bytes so that names that aren't valid UTF-8 are matched as they are.
+pub(crate) fn tracked_files(repo: &Path) -> io::Result<Vec<Vec<u8>>> {
+    if repo.join(".git").exists() {
+        read_git_index_bytes(repo)
+    } else {
+        find_files_bytes(repo, &Filter::new(&["**"]))
+    }
+}
+
+fn repo_workflows(repo: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut workflows: Vec<PathBuf> = fs::read_dir(repo.join(".github/workflows"))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "yml" || extension == "yaml"))
+        .collect();
+    workflows.sort();
+    Ok(workflows)
+}
+
+// Workflows are named after their file
+fn read_workflows(paths: &[PathBuf]) -> io::Result<Vec<(String, Workflow)>> {
+    paths.iter().map(|path| Ok((workflow_name(path), parse_workflow(&fs::read_to_string(path)?)))).collect()
+}
+
+fn workflow_name(path: &Path) -> String {
+    path.file_name().map_or(path.to_string_lossy(), |name| name.to_string_lossy()).into_owned()
+}
+
+fn match_paths(args: &[String], out: &mut dyn Write, explain: bool) -> io::Result<ExitCode> {
+    let args = parse_args(args)?;
+    check_format(&args.format, &["text", "json"])?;
+    if args.positionals.is_empty() {
+        return Err(usage_error("no patterns given"));
+    }
+
+    let globs: Vec<Glob> = args.positionals.iter().map(|pattern| Glob::with_dialect(pattern, args.dialect)).collect();
+    let filter = Filter::from_globs(globs);
+    let paths = match args.after_separator {
+        Some(paths) => paths,
+        None => io::stdin().lock().lines().collect::<io::Result<_>>()?,
+    };
+
+    if args.format == "text" {
+        for (idx, glob) in filter.globs().iter().enumerate() {
+            for error in glob.errors() {
+                writeln!(out, "pattern {}: error: {}", idx, error.message)?;
+                writeln!(out, "{}", caret(glob.pattern(), error.offset, error.len))?;
+            }
+        }
+    }
+
+    let mut all_included = true;
+    let mut results = Vec::new();
+    for original in &paths {
+        let path = match args.normalization.apply(original) {
+            Ok(path) => path,
+            Err(error) => {
+                all_included = false;
+                if args.format == "json" {
+                    results.push(Json::object(vec![
+                        ("path", original.as_str().into()),
+                        ("included", false.into()),
+                        ("deciding_pattern", Json::Null),
+                        ("error", error.message.as_str().into()),
+                    ]));
+                } else {
+                    writeln!(out, "{}: error: {}", original, error.message)?;
+                }
+                continue;
+            }
+        };
+        let path = path.as_ref();
+        let deciding_glob = filter.deciding_glob(path);
+        let included = filter.is_match(path);
+        all_included &= included;
+
+        if args.format == "json" {
+            let mut fields = vec![
+                ("path", original.as_str().into()),
+                ("included", included.into()),
+                ("deciding_pattern", deciding_glob.into()),
+            ];
+            if explain {
+                fields.push(("steps", explain_steps(&filter, path)));
+            }
+            results.push(Json::object(fields));
+            continue;
+        }
+
+        writeln!(out, "{}: {}", original, decision(&filter, path))?;
+        if explain {
+            write_steps(out, &filter, path)?;
+        }
+    }
+
+    if args.format == "json" {
+        let patterns: Vec<Json> = filter
+            .globs()
+            .iter()
+            .enumerate()
+            .map(|(idx, glob)| {
+                let errors: Vec<Json> = glob
+                    .errors()
+                    .iter()
+                    .map(|error| {
+                        Json::object(vec![
+                            ("offset", error.offset.into()),
+                            ("length", error.len.into()),
+                            ("message", error.message.as_str().into()),
+                        ])
+                    })
+                    .collect();
+                Json::object(vec![
+                    ("index", idx.into()),
+                    ("pattern", glob.pattern().into()),
+                    ("negation", glob.is_negation().into()),
+                    ("errors", errors.into()),
+                ])
+            })
+            .collect();
+        let command = if explain { "explain" } else { "match" };
+        let document =
+            json_document(command, Json::object(vec![("patterns", patterns.into()), ("results", results.into())]));
+        writeln!(out, "{}", document)?;
+    }
+
+    Ok(if all_included { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
+
+// How the filter treats the path, naming the pattern that decided
+pub(crate) fn decision(filter: &Filter, path: &str) -> String {
+    match filter.deciding_glob(path) {
+        Some(idx) => format!(
+            "{} by pattern {} `{}`",
+            if filter.is_match(path) { "included" } else { "excluded" },
+            idx,
+            filter.globs()[idx].pattern()
+        ),
+        None => "not matched".to_string(),
+    }
+}
+
+// A line for each pattern of the filter, saying how it treats the path
+pub(crate) fn write_steps(out: &mut dyn Write, filter: &Filter, path: &str) -> io::Result<()> {
+    let deciding_glob = filter.deciding_glob(path);
+    for (idx, glob) in filter.globs().iter().enumerate() {
+        let effect = match (glob.is_match(path), glob.is_negation()) {
+            (false, _) => "no match",
+            (true, false) => "matches, includes",
+            (true, true) => "matches, excludes",
+        };
+        let decides = if deciding_glob == Some(idx) { "  <- decides" } else { "" };
+        writeln!(out, "  {} {}: {}{}", idx, glob.pattern(), effect, decides)?;
+    }
+    Ok(())
+}
+
+// The pattern with carets under a range of its characters
+pub(crate) fn caret(pattern: &str, offset: usize, len: usize) -> String {
+    format!("    {}\n    {}{}", pattern, " ".repeat(offset), "^".repeat(len.max(1)))
+}
+
+fn explain_steps(filter: &Filter, path: &str) -> Json {
+    let steps: Vec<Json> = filter
+        .globs()
+        .iter()
+        .enumerate()
+        .map(|(idx, glob)| {
+            let matched = glob.is_match(path);
+            let effect = match (matched, glob.is_negation()) {
+                (false, _) => None,
+                (true, false) => Some("include"),
+                (true, true) => Some("exclude"),
+            };
+            Json::object(vec![("pattern", idx.into()), ("matched", matched.into()), ("effect", effect.into())])
+        })
+        .collect();
+    steps.into()
+}
+
+fn which_workflows(args: &[String], out: &mut dyn Write) -> io::Result<ExitCode> {
+    let args = parse_args(args)?;
+    check_format(&args.format, &["text", "json"])?;
+    let paths: Vec<&str> = args.positionals.iter().map(|path| path.as_str()).collect();
+    if paths.is_empty() {
+        return Err(usage_error("no paths given"));
+    }
+
+    let mut json_workflows = Vec::new();
+    let mut triggered_count = 0;
+    for path in &repo_workflows(&args.repo)? {
+        let workflow = parse_workflow(&fs::read_to_string(path)?);
+        let name = workflow_name(path);
+        let mut json_events = Vec::new();
+
+        for event in workflow.events.iter().filter(|event| PATH_EVENTS.contains(&event.name.as_str())) {
+            let runs_for_branch = args.branch.as_ref().is_none_or(|branch| event.runs_for_branch(branch));
+            let decisions: Vec<_> = paths.iter().map(|changed| (*changed, event.decide(changed))).collect();
+            let triggered = runs_for_branch && decisions.iter().any(|(_, decision)| decision.triggers);
+
+            let pattern_at = |filter: Option<&str>, idx: Option<usize>| {
+                let patterns = match filter? {
+                    "paths" => event.paths.as_ref()?,
+                    _ => event.paths_ignore.as_ref()?,
+                };
+                patterns.get(idx?)
+            };
+
+            if args.format == "json" {
+                let decisions: Vec<Json> = decisions
+                    .iter()
+                    .map(|(changed, decision)| {
+                        let pattern = pattern_at(decision.filter, decision.pattern_index).map(|pattern| {
+                            Json::object(vec![
+                                ("index", decision.pattern_index.into()),
+                                ("pattern", pattern.glob.pattern().into()),
+                                ("line", pattern.line.into()),
+                                ("column", pattern.column.into()),
+                            ])
+                        });
+                        Json::object(vec![
+                            ("path", (*changed).into()),
+                            ("triggers", decision.triggers.into()),
+                            ("filter", decision.filter.into()),
+                            ("deciding_pattern", pattern.into()),
+                        ])
+                    })
+                    .collect();
+                json_events.push(Json::object(vec![
+                    ("event", event.name.as_str().into()),
+                    ("triggered", triggered.into()),
+                    ("runs_for_branch", runs_for_branch.into()),
+                    ("decisions", decisions.into()),
+                ]));
+            } else if triggered {
+                let (changed, decision) = decisions.iter().find(|(_, decision)| decision.triggers).unwrap();
+                let reason = match (decision.filter, pattern_at(decision.filter, decision.pattern_index)) {
+                    (Some(filter), Some(pattern)) => format!(
+                        "{} {} by {}[{}] `{}` at line {}",
+                        changed,
+                        if filter == "paths" { "included" } else { "re-included" },
+                        filter,
+                        decision.pattern_index.unwrap_or_default(),
+                        pattern.glob.pattern(),
+                        pattern.line
+                    ),
+                    (Some(filter), None) => format!("{} matches no pattern of {}", changed, filter),
+                    (None, _) => "no path filter".to_string(),
+                };
+                writeln!(out, "{} on.{}: {}", name, event.name, reason)?;
+            }
+            triggered_count += triggered as usize;
+        }
+
+        if args.format == "json" {
+            json_workflows.push(Json::object(vec![
+                ("file", path.to_string_lossy().as_ref().into()),
+                ("name", workflow.name.into()),
+                ("events", json_events.into()),
+            ]));
+        }
+    }
+
+    if args.format == "json" {
+        let paths: Vec<Json> = paths.iter().map(|path| (*path).into()).collect();
+        let document = json_document(
+            "which-workflows",
+            Json::object(vec![
+                ("paths", paths.into()),
+                ("branch", args.branch.into()),
+                ("workflows", json_workflows.into()),
+            ]),
+        );
+        writeln!(out, "{}", document)?;
+    }
+
+    Ok(if triggered_count > 0 { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
+
+// A check of `lint`, described for the SARIF output
+struct LintRule {
+    id: &'static str,
+    name: &'static str,
+    summary: &'static str,
+    description: &'static str,
+    level: &'static str,
+}
+
+const LINT_RULES: &[LintRule] = &[
+    LintRule {
+        id: "yaml-quoting",
+        name: "UnquotedFilterPattern",
+        summary: "Filter pattern needs quotes",
+        description: "YAML reads an unquoted pattern starting with `*`, `!`, `[` or `{`, or one with brackets \
+                      inside a flow sequence, as something other than a string, and GitHub then rejects the \
+                      whole workflow.",
+        // A quoting problem keeps the whole workflow from loading
+        level: "error",
+    },
+    LintRule {
+        id: "invalid-pattern",
+        name: "InvalidFilterPattern",
+        summary: "Filter pattern can never match",
+        description: "The pattern has an unclosed `[`, an empty `[]`, or a `?` or `+` with no character before \
+                      it, which makes it match nothing.",
+        level: "error",
+    },
+    LintRule {
+        id: "pattern-fix",
+        name: "MistakenPathPattern",
+        summary: "Path pattern with a common mistake",
+        description: "The pattern uses backslashes, a leading `./` or `/`, a trailing `/`, braces or `?` as \
+                      \"any character\", which GitHub doesn't read the way they are usually meant.",
+        level: "warning",
+    },
+    LintRule {
+        id: "dead-pattern",
+        name: "DeadPathPattern",
+        summary: "Path pattern matches no tracked file",
+        description: "The `paths` or `paths-ignore` pattern matches none of the files tracked in the \
+                      repository, which is often a typo in a directory name.",
+        level: "warning",
+    },
+    LintRule {
+        id: "shadowed-pattern",
+        name: "ShadowedPathPattern",
+        summary: "Path pattern has no effect where it is",
+        description: "Every tracked file the pattern matches is matched again by a later pattern of the filter, \
+                      which decides instead, or the pattern is a `!` negation and the patterns before it include \
+                      none of its files. The entry is usually in the wrong place.",
+        level: "warning",
+    },
+];
+
+// A problem found by `lint`, whatever check found it
+pub(crate) struct Finding {
+    // One of the LINT_RULES
+    pub(crate) rule: &'static str,
+    pub(crate) file: String,
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+    // Where the pattern ends as written in the file, quotes included, exclusive
+    pub(crate) end_column: usize,
+    pub(crate) event: String,
+    pub(crate) filter: String,
+    pub(crate) index: usize,
+    // The pattern, unquoted
+    pub(crate) pattern: String,
+    // The characters of the pattern the finding is about, as an offset and a length
+    pub(crate) span: (usize, usize),
+    pub(crate) message: String,
+    // Replacements for the pattern
+    pub(crate) suggestions: Vec<String>,
+    // Whether --fix applied the first suggestion
+    pub(crate) fixed: bool,
+}
+
+impl Finding {
+    pub(crate) fn severity(&self) -> &'static str {
+        LINT_RULES.iter().find(|rule| rule.id == self.rule).map_or("warning", |rule| rule.level)
+    }
+
+    pub(crate) fn message(&self) -> String {
+        let message = format!("`{}` in on.{}.{}: {}", self.pattern, self.event, self.filter, self.message);
+        match self.hint() {
+            Some(hint) => format!("{}; {}", message, hint),
+            None => message,
+        }
+    }
+
+    pub(crate) fn hint(&self) -> Option<String> {
+        let quoted = self.suggestions.first()?;
+        let suggestions: Vec<String> = self.suggestions.iter().map(|suggestion| format!("`{}`", suggestion)).collect();
+        let suggestions = suggestions.join(", ");
+
+        Some(match (self.rule, self.fixed) {
+            ("yaml-quoting", false) => format!("quote it as {}", quoted),
+            ("yaml-quoting", true) => format!("quoted as {}", quoted),
+            ("dead-pattern", _) => format!("did you mean {}?", suggestions),
+            (_, false) => format!("use {}", suggestions),
+            (_, true) => format!("replaced with {}", suggestions),
+        })
+    }
+
+    // The content of the workflow file with the first suggestion applied, if the finding has one
+    pub(crate) fn apply(&self, content: &str) -> Option<String> {
+        let replacement = self.suggestions.first()?;
+
+        let applied = match self.rule {
+            "yaml-quoting" => {
+                let problem = QuotingProblem {
+                    event: self.event.clone(),
+                    key: self.filter.clone(),
+                    index: self.index,
+                    line: self.line,
+                    column: self.column,
+                    text: self.pattern.clone(),
+                    message: self.message.clone(),
+                    quoted: replacement.clone(),
+                };
+                apply_quoting(content, &[problem])
+            }
+            _ => {
+                let fix = PatternFix {
+                    event: self.event.clone(),
+                    key: self.filter.clone(),
+                    index: self.index,
+                    pattern: SourcePattern { glob: Glob::new(&self.pattern), line: self.line, column: self.column },
+                    fix: Fix { message: self.message.clone(), replacements: self.suggestions.clone() },
+                };
+                apply_fixes(content, &[fix])
+            }
+        };
+        (applied != content).then_some(applied)
+    }
+
+    // An `::error` or `::warning` workflow command, which GitHub shows on the line of the pattern.
+    // Fixed findings are only a `::notice`, as there is nothing left to do about them.
+    fn annotation(&self) -> String {
+        let command = if self.fixed { "notice" } else { self.severity() };
+        let file = self.file.strip_prefix("./").unwrap_or(&self.file);
+
+        format!(
+            "::{} file={},line={},col={},endColumn={},title={}::{}",
+            command,
+            escape_property(file),
+            self.line,
+            self.column,
+            self.end_column,
+            escape_property(&format!("pathglob {}", self.rule)),
+            escape_data(&self.message())
+        )
+    }
+
+    fn json(&self) -> Json {
+        Json::object(vec![
+            ("rule", self.rule.into()),
+            ("severity", self.severity().into()),
+            ("file", self.file.as_str().into()),
+            ("line", self.line.into()),
+            ("column", self.column.into()),
+            ("event", self.event.as_str().into()),
+            ("filter", self.filter.as_str().into()),
+            ("index", self.index.into()),
+            ("pattern", self.pattern.as_str().into()),
+            ("span", Json::object(vec![("offset", self.span.0.into()), ("length", self.span.1.into())])),
+            ("message", self.message.as_str().into()),
+            ("suggestions", self.suggestions.clone().into()),
+            ("fixed", self.fixed.into()),
+        ])
+    }
+}
+
+fn lint(args: &[String], out: &mut dyn Write) -> io::Result<ExitCode> {
+    let args = parse_args(args)?;
+    check_format(&args.format, &["text", "json", "github", "sarif"])?;
+    let files = args.repo_files()?;
+
+    let mut findings = Vec::new();
+    for path in &args.workflows()? {
+        let content = fs::read_to_string(path)?;
+        let (workflow_findings, fixed) = lint_workflow(&path.to_string_lossy(), &content, &files, args.fix);
+        if fixed != content {
+            fs::write(path, fixed)?;
+        }
+        findings.extend(workflow_findings);
+    }
+
+    if args.format == "json" {
+        let findings: Vec<Json> = findings.iter().map(|finding| finding.json()).collect();
+        writeln!(out, "{}", json_document("lint", Json::object(vec![("findings", findings.into())])))?;
+    } else if args.format == "github" {
+        for finding in &findings {
+            writeln!(out, "{}", finding.annotation())?;
+        }
+    } else if args.format == "sarif" {
+        writeln!(out, "{}", sarif(&findings, &args.repo))?;
+    } else {
+        for finding in &findings {
+            writeln!(out, "{}:{}:{}: {}", finding.file, finding.line, finding.column, finding.message())?;
+            writeln!(out, "{}", caret(&finding.pattern, finding.span.0, finding.span.1))?;
+        }
+    }
+
+    let remaining = findings.iter().filter(|finding| !finding.fixed).count();
+    Ok(if remaining == 0 { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
+
+// Runs every check on the content of a workflow file. With `fix`, the findings that have a fix are
+// marked as fixed and the returned content has them applied.
+pub(crate) fn lint_workflow(file: &str, content: &str, files: &[Vec<u8>], fix: bool) -> (Vec<Finding>, String) {
+    let mut findings = Vec::new();
+    let mut content = content.to_string();
+
+    let quoting_problems = find_quoting_problems(&content);
+    for problem in &quoting_problems {
+        let mut finding = Finding {
+            rule: "yaml-quoting",
+            file: file.to_string(),
+            line: problem.line,
+            column: problem.column,
+            end_column: problem.column + problem.text.chars().count(),
+            event: problem.event.clone(),
+            filter: problem.key.clone(),
+            index: problem.index,
+            pattern: problem.text.clone(),
+            // A problem with the first character, unless the brackets inside are the problem
+            span: if problem.text.starts_with(['*', '!', '[', '{']) {
+                (0, 1)
+            } else {
+                (0, problem.text.chars().count())
+            },
+            message: problem.message.clone(),
+            suggestions: vec![problem.quoted.clone()],
+            fixed: false,
+        };
+        // Only the findings whose pattern is still where they point get fixed
+        finding.fixed = fix && finding.apply(&content).is_some();
+        findings.push(finding);
+    }
+    // The other checks need the patterns YAML can read
+    if fix {
+        content = apply_quoting(&content, &quoting_problems);
+    }
+
+    let workflow = parse_workflow(&content);
+    let document = yaml::parse(&content);
+    let scalars = document.descendant_scalars();
+    let end_column = |line: usize, column: usize| {
+        let scalar = scalars.iter().find(|scalar| (scalar.line, scalar.column) == (line, column));
+        column + scalar.map_or(0, |scalar| scalar.raw.chars().count())
+    };
+
+    for event in &workflow.events {
+        for (key, patterns) in event.filters() {
+            for (index, pattern) in patterns.iter().enumerate() {
+                for error in pattern.glob.errors() {
+                    findings.push(Finding {
+                        rule: "invalid-pattern",
+                        file: file.to_string(),
+                        line: pattern.line,
+                        column: pattern.column,
+                        end_column: end_column(pattern.line, pattern.column),
+                        event: event.name.clone(),
+                        filter: key.to_string(),
+                        index,
+                        pattern: pattern.glob.pattern().to_string(),
+                        span: (error.offset, error.len),
+                        message: error.message.clone(),
+                        suggestions: vec![],
+                        fixed: false,
+                    });
+                }
+            }
+        }
+    }
+
+    let fixes = find_fixes(&workflow);
+    for pattern_fix in &fixes {
+        let mut finding = Finding {
+            rule: "pattern-fix",
+            file: file.to_string(),
+            line: pattern_fix.pattern.line,
+            column: pattern_fix.pattern.column,
+            end_column: end_column(pattern_fix.pattern.line, pattern_fix.pattern.column),
+            event: pattern_fix.event.clone(),
+            filter: pattern_fix.key.clone(),
+            index: pattern_fix.index,
+            pattern: pattern_fix.pattern.glob.pattern().to_string(),
+            span: (0, pattern_fix.pattern.glob.pattern().chars().count()),
+            message: pattern_fix.fix.message.clone(),
+            suggestions: pattern_fix.fix.replacements.clone(),
+            fixed: false,
+        };
+        finding.fixed = fix && finding.apply(&content).is_some();
+        findings.push(finding);
+    }
+
+    // A pattern with an error, a fix or missing quotes is usually dead too, and those findings
+    // already say why
+    let has_finding = |line: usize, column: usize| {
+        fixes.iter().any(|fix| (fix.pattern.line, fix.pattern.column) == (line, column))
+            || !fix && quoting_problems.iter().any(|problem| (problem.line, problem.column) == (line, column))
+    };
+    for dead_pattern in find_dead_patterns(&workflow, files) {
+        let pattern = &dead_pattern.pattern;
+        if !pattern.glob.errors().is_empty() || has_finding(pattern.line, pattern.column) {
+            continue;
+        }
+        findings.push(Finding {
+            rule: "dead-pattern",
+            file: file.to_string(),
+            line: pattern.line,
+            column: pattern.column,
+            end_column: end_column(pattern.line, pattern.column),
+            event: dead_pattern.event.clone(),
+            filter: dead_pattern.key.clone(),
+            index: dead_pattern.index,
+            pattern: pattern.glob.pattern().to_string(),
+            span: (0, pattern.glob.pattern().chars().count()),
+            message: "matches no tracked file".to_string(),
+            suggestions: dead_pattern.suggestion.into_iter().collect(),
+            fixed: false,
+        });
+    }
+
+    for shadowed_pattern in find_shadowed_patterns(&workflow, files) {
+        let pattern = &shadowed_pattern.pattern;
+        if !pattern.glob.errors().is_empty() || has_finding(pattern.line, pattern.column) {
+            continue;
+        }
+        findings.push(Finding {
+            rule: "shadowed-pattern",
+            file: file.to_string(),
+            line: pattern.line,
+            column: pattern.column,
+            end_column: end_column(pattern.line, pattern.column),
+            event: shadowed_pattern.event.clone(),
+            filter: shadowed_pattern.key.clone(),
+            index: shadowed_pattern.index,
+            pattern: pattern.glob.pattern().to_string(),
+            span: (0, pattern.glob.pattern().chars().count()),
+            message: shadowed_pattern.message,
+            suggestions: vec![],
+            fixed: false,
+        });
+    }
+
+    if fix && !fixes.is_empty() {
+        content = apply_fixes(&content, &fixes);
+    }
+    (findings, content)
+}
+
+// A SARIF 2.1.0 log with a single run, for code scanning dashboards. Files are given relative to the
+// repository, which SARIF consumers know as %SRCROOT%.
+fn sarif(findings: &[Finding], repo: &Path) -> Json {
+    let rules: Vec<Json> = LINT_RULES
+        .iter()
+        .map(|rule| {
+            Json::object(vec![
+                ("id", rule.id.into()),
+                ("name", rule.name.into()),
+                ("shortDescription", Json::object(vec![("text", rule.summary.into())])),
+                ("fullDescription", Json::object(vec![("text", rule.description.into())])),
+                ("defaultConfiguration", Json::object(vec![("level", rule.level.into())])),
+            ])
+        })
+        .collect();
+
+    let results: Vec<Json> = findings
+        .iter()
+        .map(|finding| {
+            let rule_index = LINT_RULES.iter().position(|rule| rule.id == finding.rule);
+            // There is nothing left to do about a fixed finding
+            let level = if finding.fixed { "note" } else { finding.severity() };
+
+            let mut artifact = vec![];
+            match Path::new(&finding.file).strip_prefix(repo) {
+                Ok(relative) => {
+                    artifact.push(("uri", relative.to_string_lossy().replace('\\', "/").into()));
+                    artifact.push(("uriBaseId", "%SRCROOT%".into()));
+                }
+                Err(_) => artifact.push(("uri", finding.file.replace('\\', "/").into())),
+            }
+            let region = Json::object(vec![
+                ("startLine", finding.line.into()),
+                ("startColumn", finding.column.into()),
+                ("endColumn", finding.end_column.into()),
+            ]);
+            let location = Json::object(vec![(
+                "physicalLocation",
+                Json::object(vec![("artifactLocation", Json::object(artifact)), ("region", region)]),
+            )]);
+
+            Json::object(vec![
+                ("ruleId", finding.rule.into()),
+                ("ruleIndex", rule_index.into()),
+                ("level", level.into()),
+                ("message", Json::object(vec![("text", finding.message().into())])),
+                ("locations", vec![location].into()),
+                (
+                    "properties",
+                    Json::object(vec![
+                        ("event", finding.event.as_str().into()),
+                        ("filter", finding.filter.as_str().into()),
+                        ("index", finding.index.into()),
+                        ("pattern", finding.pattern.as_str().into()),
+                        ("suggestions", finding.suggestions.clone().into()),
+                        ("fixed", finding.fixed.into()),
+                    ]),
+                ),
+            ])
+        })
+        .collect();
+
+    let driver = Json::object(vec![
+        ("name", "pathglob".into()),
+        ("version", env!("CARGO_PKG_VERSION").into()),
+        ("rules", rules.into()),
+    ]);
+    let run = Json::object(vec![
+        ("tool", Json::object(vec![("driver", driver)])),
+        ("columnKind", "unicodeCodePoints".into()),
+        ("results", results.into()),
+    ]);
+    Json::object(vec![
+        ("$schema", "https://json.schemastore.org/sarif-2.1.0.json".into()),
+        ("version", "2.1.0".into()),
+        ("runs", vec![run].into()),
+    ])
+}
+
+// Workflow command values can't contain line breaks, and `%` starts an escape
+fn escape_data(value: &str) -> String {
+    value.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+// Property values additionally end at `,` and `:`
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+fn repl(args: &[String], out: &mut dyn Write) -> io::Result<ExitCode> {
+    let args = parse_args(args)?;
+    check_format(&args.format, &["text"])?;
+    let files: Vec<String> = args.repo_files()?.iter().map(|file| String::from_utf8_lossy(file).into_owned()).collect();
+    // Prompts and colors are only for people typing at a terminal
+    let interactive = io::stdin().is_terminal() && io::stdout().is_terminal();
+
+    run_repl(&mut io::stdin().lock(), out, &files, args.dialect, interactive)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+// The messages are JSON-RPC whatever the format, so only the default is accepted
+fn lsp(args: &[String], out: &mut dyn Write) -> io::Result<ExitCode> {
+    let args = parse_args(args)?;
+    check_format(&args.format, &["text"])?;
+
+    run_lsp(&mut io::stdin().lock(), out)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn coverage(args: &[String], out: &mut dyn Write) -> io::Result<ExitCode> {
+    let args = parse_args(args)?;
+    check_format(&args.format, &["text", "json"])?;
+    let files = args.repo_files()?;
+
+    let workflows: Vec<Workflow> =
+        read_workflows(&args.workflows()?)?.into_iter().map(|(_, workflow)| workflow).collect();
+    let branch = args.branch.as_deref().unwrap_or("main");
+    let uncovered = find_uncovered_files(&workflows, &files, branch);
+
+    if args.format == "json" {
+        let directories: Vec<Json> = uncovered
+            .iter()
+            .map(|directory| {
+                Json::object(vec![
+                    ("dir", directory.dir.as_str().into()),
+                    ("files", directory.files.clone().into()),
+                    ("file_count", directory.file_count.into()),
+                ])
+            })
+            .collect();
+        let document = json_document(
+            "coverage",
+            Json::object(vec![("branch", branch.into()), ("directories", directories.into())]),
+        );
+        writeln!(out, "{}", document)?;
+    } else {
+        for directory in &uncovered {
+            let dir = if directory.dir.is_empty() { "." } else { &directory.dir };
+            writeln!(out, "{}/ ({} of {} files)", dir, directory.files.len(), directory.file_count)?;
+            for file in &directory.files {
+                writeln!(out, "  {}", file)?;
+            }
+        }
+    }
+
+    Ok(if uncovered.is_empty() { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
+
+fn matrix(args: &[String], out: &mut dyn Write) -> io::Result<ExitCode> {
+    let args = parse_args(args)?;
+    check_format(&args.format, &["text", "json", "csv", "markdown"])?;
+    let files = args.repo_files()?;
+
+    let workflows = read_workflows(&args.workflows()?)?;
+    let columns: Vec<(&str, &Workflow)> = workflows.iter().map(|(name, workflow)| (name.as_str(), workflow)).collect();
+    let matrix = trigger_matrix(&columns, &files, args.depth);
+
+    match args.format.as_str() {
+        "csv" => write!(out, "{}", matrix.to_csv())?,
+        "json" => writeln!(out, "{}", json_document("matrix", matrix.json()))?,
+        _ => write!(out, "{}", matrix.to_markdown())?,
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn diff(args: &[String], out: &mut dyn Write) -> io::Result<ExitCode> {
+    let args = parse_args(args)?;
+    check_format(&args.format, &["text", "json"])?;
+    let [old, new] = args.positionals.as_slice() else {
+        return Err(usage_error("diff needs an old and a new workflow"));
+    };
+    let files = args.repo_files()?;
+
+    let old = parse_workflow(&fs::read_to_string(old)?);
+    let new = parse_workflow(&fs::read_to_string(new)?);
+    let changes = diff_workflows(&old, &new, &files);
+
+    if args.format == "json" {
+        let changes: Vec<Json> = changes
+            .iter()
+            .map(|change| {
+                Json::object(vec![
+                    ("event", change.event.as_str().into()),
+                    ("included", change.included.clone().into()),
+                    ("excluded", change.excluded.clone().into()),
+                ])
+            })
+            .collect();
+        writeln!(out, "{}", json_document("diff", Json::object(vec![("changes", changes.into())])))?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    for change in &changes {
+        writeln!(out, "on.{}:", change.event)?;
+        for file in &change.included {
+            writeln!(out, "  + {}", file)?;
+        }
+        for file in &change.excluded {
+            writeln!(out, "  - {}", file)?;
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,125 @@
+// CODEOWNERS files: gitignore-like patterns where the last matching line decides the owners.
+// See https://docs.github.com/en/repositories/managing-your-repositorys-settings-and-features/customizing-your-repository/about-code-owners
+
+use crate::wildcard::{gitignore_style_segments, Syntax};
+use crate::{Dialect, Glob, Pattern, Segment};
+
+// CODEOWNERS doesn't support character ranges or escapes, unlike .gitignore
+const CODEOWNERS_SYNTAX: Syntax =
+    Syntax { classes: false, escapes: false, braces: false, brace_ranges: false, extglobs: false };
+
+// Parses a pattern for Dialect::Codeowners. Negation isn't supported, so a leading ! is literal.
+pub(crate) fn parse_pattern(pattern: &str) -> Vec<(Pattern, bool)> {
+    // "docs/*" owns the files directly in docs, but not the ones in its subdirectories
+    let descend = !pattern.ends_with("/*") && pattern != "*";
+    let segments: Vec<Segment> = gitignore_style_segments(pattern, 0, CODEOWNERS_SYNTAX, descend);
+
+    vec![(Pattern { segments }, false)]
+}
+
+/// A parsed CODEOWNERS file.
+#[derive(Debug, Clone)]
+pub struct CodeOwners {
+    rules: Vec<OwnerRule>,
+    errors: Vec<CodeOwnersError>,
+}
+
+#[derive(Debug, Clone)]
+pub struct OwnerRule {
+    pub glob: Glob,
+    /// `@user`, `@org/team` or email owners; empty when the line removes ownership
+    pub owners: Vec<String>,
+    /// 1-based line number in the file
+    pub line: usize,
+}
+
+/// A line that GitHub would reject, and which is skipped when resolving owners.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeOwnersError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl CodeOwners {
+    pub fn parse(content: &str) -> CodeOwners {
+        let mut rules = Vec::new();
+        let mut errors = Vec::new();
+
+        for (idx, raw_line) in content.lines().enumerate() {
+            let line = idx + 1;
+
+            let mut fields = raw_line.split_whitespace();
+
+            let pattern = match fields.next() {
+                Some(pattern) if !pattern.starts_with('#') => pattern,
+                _ => continue, // Blank line or comment
+            };
+            // Comments may also follow the owners on the same line
+            let owners: Vec<String> =
+                fields.take_while(|field| !field.starts_with('#')).map(|owner| owner.to_string()).collect();
+
+            if let Some(message) = unsupported_syntax(pattern) {
+                errors.push(CodeOwnersError { line, message: message.to_string() });
+                continue;
+            }
+            if let Some(owner) = owners.iter().find(|owner| !is_valid_owner(owner)) {
+                errors.push(CodeOwnersError { line, message: format!("invalid owner '{}'", owner) });
+                continue;
+            }
+
+            rules.push(OwnerRule { glob: Glob::with_dialect(pattern, Dialect::Codeowners), owners, line });
+        }
+
+        CodeOwners { rules, errors }
+    }
+
+    pub fn rules(&self) -> &[OwnerRule] {
+        &self.rules
+    }
+
+    pub fn errors(&self) -> &[CodeOwnersError] {
+        &self.errors
+    }
+
+    /// The rule that decides the owners of a path: the last one matching it.
+    pub fn rule_for(&self, path: &str) -> Option<&OwnerRule> {
+        self.rules.iter().rev().find(|rule| rule.glob.is_match(path))
+    }
+
+    /// The owners of a path, empty if no rule assigns any.
+    pub fn owners_of(&self, path: &str) -> &[String] {
+        self.rule_for(path).map(|rule| rule.owners.as_slice()).unwrap_or(&[])
+    }
+}
+
+fn unsupported_syntax(pattern: &str) -> Option<&'static str> {
+    if pattern.starts_with('!') {
+        Some("negation with '!' is not supported in CODEOWNERS")
+    } else if pattern.contains('[') || pattern.contains(']') {
+        Some("character ranges with '[ ]' are not supported in CODEOWNERS")
+    } else if pattern.starts_with("\\#") {
+        Some("escaping a pattern starting with '#' is not supported in CODEOWNERS")
+    } else {
+        None
+    }
+}
+
+fn is_valid_owner(owner: &str) -> bool {
+    if let Some(handle) = owner.strip_prefix('@') {
+        // "@user" or "@org/team"
+        let mut parts = handle.split('/');
+        let valid_part = |part: &str| {
+            !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+        };
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(user), None, None) => valid_part(user),
+            (Some(org), Some(team), None) => valid_part(org) && valid_part(team),
+            _ => false,
+        }
+    } else {
+        match owner.split_once('@') {
+            Some((local, domain)) => !local.is_empty() && domain.contains('.') && !domain.contains('@'),
+            None => false,
+        }
+    }
+}
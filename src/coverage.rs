@@ -0,0 +1,57 @@
+use std::collections::BTreeMap;
+
+use crate::Workflow;
+
+/// The files of a directory that no workflow runs for, see [`find_uncovered_files`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UncoveredDirectory {
+    /// The directory, or an empty string for the repository root
+    pub dir: String,
+    /// The uncovered files directly inside the directory, as full paths
+    pub files: Vec<String>,
+    /// How many files directly inside the directory there are, covered or not
+    pub file_count: usize,
+}
+
+impl UncoveredDirectory {
+    /// Whether none of the directory's own files triggers a workflow.
+    pub fn is_fully_uncovered(&self) -> bool {
+        self.files.len() == self.file_count
+    }
+}
+
+/// Finds the files whose change, pushed to `branch`, would run none of the workflows, grouped by
+/// the directory they are in.
+///
+/// Only `push` events count. Directories come in path order and those where every file is covered
+/// are left out. Files can be given as strings or bytes; the ones that are not valid UTF-8 are
+/// reported lossily.
+pub fn find_uncovered_files<P: AsRef<[u8]>>(
+    workflows: &[Workflow],
+    files: &[P],
+    branch: &str,
+) -> Vec<UncoveredDirectory> {
+    let push_events: Vec<_> = workflows
+        .iter()
+        .filter_map(|workflow| workflow.event("push"))
+        .filter(|event| event.runs_for_branch(branch))
+        .collect();
+
+    let mut directories: BTreeMap<&[u8], UncoveredDirectory> = BTreeMap::new();
+    for file in files {
+        let file = file.as_ref();
+        let dir = file.iter().rposition(|byte| *byte == b'/').map_or(&file[..0], |idx| &file[..idx]);
+        let directory = directories.entry(dir).or_insert_with(|| UncoveredDirectory {
+            dir: String::from_utf8_lossy(dir).into_owned(),
+            files: vec![],
+            file_count: 0,
+        });
+
+        directory.file_count += 1;
+        if !push_events.iter().any(|event| event.is_triggered_by(&[file])) {
+            directory.files.push(String::from_utf8_lossy(file).into_owned());
+        }
+    }
+
+    directories.into_values().filter(|directory| !directory.files.is_empty()).collect()
+}
@@ -0,0 +1,48 @@
+use crate::workflow::PATH_EVENTS;
+use crate::Workflow;
+
+/// The files whose changes start or stop running a workflow event, see [`diff_workflows`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterChange {
+    pub event: String,
+    /// Files that trigger the event with the new filters but didn't with the old ones
+    pub included: Vec<String>,
+    /// Files that triggered the event with the old filters but no longer do
+    pub excluded: Vec<String>,
+}
+
+/// Compares the path filters of two versions of a workflow over the repository's files.
+///
+/// Each `push`, `pull_request` or `pull_request_target` event of either version is compared on its
+/// own; an event that was added or removed counts as triggered by nothing in the version that lacks
+/// it. Branch filters are not considered, and events whose files didn't change are left out.
+/// Files can be given as strings or bytes; the ones that are not valid UTF-8 are reported lossily.
+pub fn diff_workflows<P: AsRef<[u8]>>(old: &Workflow, new: &Workflow, files: &[P]) -> Vec<FilterChange> {
+    let mut events: Vec<&str> = Vec::new();
+    for event in old.events.iter().chain(&new.events) {
+        if PATH_EVENTS.contains(&event.name.as_str()) && !events.contains(&event.name.as_str()) {
+            events.push(&event.name);
+        }
+    }
+
+    let triggers = |workflow: &Workflow, event: &str, file: &[u8]| {
+        workflow.event(event).is_some_and(|event| event.is_triggered_by(&[file]))
+    };
+
+    events
+        .into_iter()
+        .map(|event| {
+            let mut change = FilterChange { event: event.to_string(), included: vec![], excluded: vec![] };
+            for file in files {
+                let file = file.as_ref();
+                match (triggers(old, event, file), triggers(new, event, file)) {
+                    (false, true) => change.included.push(String::from_utf8_lossy(file).into_owned()),
+                    (true, false) => change.excluded.push(String::from_utf8_lossy(file).into_owned()),
+                    _ => {}
+                }
+            }
+            change
+        })
+        .filter(|change| !change.included.is_empty() || !change.excluded.is_empty())
+        .collect()
+}
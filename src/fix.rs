@@ -0,0 +1,234 @@
+// Rewrites for the mistakes people commonly make in workflow path filters, most of which leave a
+// pattern that silently matches nothing.
+
+use crate::wildcard::{self, Node, NodeKind, Syntax};
+use crate::yaml::{self, ScalarStyle};
+use crate::{SourcePattern, Workflow};
+
+// GitHub's syntax with `{a,b}` added, so that everything but braces survives a round trip
+const BRACES_ONLY: Syntax =
+    Syntax { classes: false, escapes: false, braces: true, brace_ranges: false, extglobs: false };
+
+/// A rewrite of a pattern that likely doesn't mean what its author intended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    /// What is wrong with the pattern
+    pub message: String,
+    /// The patterns to write instead, more than one when braces were expanded
+    pub replacements: Vec<String>,
+}
+
+/// A [`Fix`] for a pattern of a workflow's `paths` or `paths-ignore` filter.
+#[derive(Debug, Clone)]
+pub struct PatternFix {
+    pub event: String,
+    /// "paths" or "paths-ignore"
+    pub key: String,
+    /// The position of the pattern in the filter
+    pub index: usize,
+    pub pattern: SourcePattern,
+    pub fix: Fix,
+}
+
+/// Detects the common authoring mistakes in a GitHub filter pattern and rewrites it.
+///
+/// These are Windows backslashes, a leading `./` or `/`, a trailing `/` or `/**/`, `{a,b}` braces
+/// (which GitHub compares literally) and `?` written as "any character", which GitHub reads as
+/// making the previous character optional. The leading `!` of a negation is kept.
+pub fn fix_pattern(pattern: &str) -> Option<Fix> {
+    let (body, prefix) = match pattern.strip_prefix('!') {
+        Some(rest) => (rest, "!"),
+        None => (pattern, ""),
+    };
+    let mut messages = Vec::new();
+    let mut body = body.to_string();
+
+    if body.contains('\\') {
+        messages.push("paths use `/` as the separator, even on Windows");
+        body = body.replace('\\', "/");
+    }
+
+    let trimmed = body.trim_start_matches("./").trim_start_matches('/');
+    if trimmed.len() != body.len() {
+        messages.push("paths are relative to the repository root and never start with `./` or `/`");
+        body = trimmed.to_string();
+    }
+
+    if let Some(dir) = body.strip_suffix("/**/") {
+        messages.push("a trailing `/**/` only matches paths ending with a slash, which changed files never have");
+        body = format!("{}/**", dir);
+    } else if let Some(dir) = body.strip_suffix('/').filter(|dir| !dir.is_empty()) {
+        messages.push("a trailing `/` only matches paths ending with a slash; `/**` matches the files in a directory");
+        body = format!("{}/**", dir);
+    }
+
+    if let Some(fixed) = fix_optional_as_any(&body) {
+        messages.push("`?` makes the previous character optional in GitHub; `*` matches any characters");
+        body = fixed;
+    }
+
+    // Braces expanding to thousands of patterns are no rewrite to suggest
+    let expanded = expand_braces(&body)?;
+    if expanded.len() > 1 {
+        messages.push("GitHub compares braces literally, so each alternative needs its own pattern");
+    }
+
+    if messages.is_empty() || body.is_empty() {
+        return None;
+    }
+    Some(Fix {
+        message: messages.join("; "),
+        replacements: expanded.iter().map(|replacement| format!("{}{}", prefix, replacement)).collect(),
+    })
+}
+
+// Replaces each run of `?` that can't be making a letter or digit optional with `*`
+fn fix_optional_as_any(body: &str) -> Option<String> {
+    let chars: Vec<char> = body.chars().collect();
+    let mut fixed = String::new();
+    let mut changed = false;
+    let mut idx = 0;
+
+    while idx < chars.len() {
+        let run = chars[idx..].iter().take_while(|c| **c == '?').count();
+        if run == 0 {
+            fixed.push(chars[idx]);
+            idx += 1;
+            continue;
+        }
+
+        let previous = idx.checked_sub(1).map(|previous| chars[previous]);
+        if run > 1 || !previous.is_some_and(|c| c.is_alphanumeric()) {
+            fixed.push('*');
+            changed = true;
+        } else {
+            fixed.push('?');
+        }
+        idx += run;
+    }
+
+    changed.then_some(fixed)
+}
+
+// Expands the braces of a pattern into the patterns they stand for, or None when there are more of
+// them than the wildcard dialects expand
+fn expand_braces(pattern: &str) -> Option<Vec<String>> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let nodes = wildcard::parse(pattern, 0, BRACES_ONLY);
+    let variants = wildcard::try_expand_braces(&nodes)?;
+
+    Some(variants.iter().map(|variant| render(variant, &chars)).collect())
+}
+
+// Writes expanded nodes back as pattern text, copying each run of stars from the source pattern
+fn render(nodes: &[Node], chars: &[char]) -> String {
+    let mut text = String::new();
+    for node in nodes {
+        match node.kind {
+            NodeKind::Literal(c) => text.push(c),
+            NodeKind::Separator => text.push('/'),
+            NodeKind::Any => text.push('?'),
+            NodeKind::Star { .. } => text.extend(chars[node.offset..].iter().take_while(|c| **c == '*')),
+            NodeKind::Class { .. } | NodeKind::Brace(_) | NodeKind::Ext(..) => {
+                unreachable!("only braces are parsed and they are expanded")
+            }
+        }
+    }
+    text
+}
+
+/// Finds the `paths` and `paths-ignore` patterns of a workflow that [`fix_pattern`] rewrites.
+pub fn find_fixes(workflow: &Workflow) -> Vec<PatternFix> {
+    let mut fixes = Vec::new();
+
+    for event in &workflow.events {
+        for (key, patterns) in event.path_filters() {
+            for (index, pattern) in patterns.iter().enumerate() {
+                if let Some(fix) = fix_pattern(pattern.glob.pattern()) {
+                    fixes.push(PatternFix {
+                        event: event.name.clone(),
+                        key: key.to_string(),
+                        index,
+                        pattern: pattern.clone(),
+                        fix,
+                    });
+                }
+            }
+        }
+    }
+
+    fixes
+}
+
+/// Applies fixes found by [`find_fixes`] to the workflow file they were found in.
+///
+/// Replacements keep the quoting of the original pattern, adding quotes where YAML needs them.
+/// Extra patterns from expanded braces go on new `- ` lines in a block sequence and after a comma
+/// in a flow sequence. Fixes whose pattern is no longer where they point are skipped.
+pub fn apply_fixes(content: &str, fixes: &[PatternFix]) -> String {
+    let document = yaml::parse(content);
+    let scalars = document.descendant_scalars();
+
+    let mut lines: Vec<String> = content.split('\n').map(|line| line.to_string()).collect();
+
+    // Back to front, so that earlier positions on the same line stay valid
+    let mut fixes: Vec<&PatternFix> = fixes.iter().collect();
+    fixes.sort_by_key(|fix| (fix.pattern.line, fix.pattern.column));
+    fixes.dedup_by_key(|fix| (fix.pattern.line, fix.pattern.column));
+
+    for fix in fixes.iter().rev() {
+        let position = (fix.pattern.line, fix.pattern.column);
+        let Some(scalar) = scalars.iter().find(|scalar| (scalar.line, scalar.column) == position) else {
+            continue;
+        };
+        if scalar.value != fix.pattern.glob.pattern() || fix.fix.replacements.is_empty() {
+            continue;
+        }
+
+        let Some(line) = lines.get_mut(scalar.line - 1) else {
+            continue;
+        };
+        let Some((start, _)) = line.char_indices().nth(scalar.column - 1) else {
+            continue;
+        };
+        if !line[start..].starts_with(&scalar.raw) {
+            continue;
+        }
+        let (before, after) = (line[..start].to_string(), line[start + scalar.raw.len()..].to_string());
+
+        let quoted: Vec<String> =
+            fix.fix.replacements.iter().map(|replacement| quote(replacement, scalar.style, scalar.in_flow)).collect();
+        let is_block_item = before.trim() == "-";
+        let replacement = if quoted.len() == 1 {
+            quoted[0].clone()
+        } else if scalar.in_flow {
+            quoted.join(", ")
+        } else if is_block_item {
+            quoted.join(&format!("\n{}", before))
+        } else {
+            format!("[{}]", quoted.join(", "))
+        };
+
+        *line = format!("{}{}{}", before, replacement, after);
+    }
+
+    lines.join("\n")
+}
+
+// Writes a pattern in the style of the scalar it replaces, quoting a plain scalar when YAML would
+// read it as something else
+fn quote(pattern: &str, style: ScalarStyle, in_flow: bool) -> String {
+    let needs_quotes = pattern.is_empty()
+        || pattern.starts_with(['*', '[', ']', '{', '}', '!', '&', '#', '|', '>', '\'', '"', '%', '@', '`', ',', '?'])
+        || pattern.starts_with("- ")
+        || pattern.contains(": ")
+        || pattern.contains(" #")
+        || pattern.ends_with([':', ' '])
+        || in_flow && pattern.contains([',', '[', ']', '{', '}']);
+
+    match style {
+        ScalarStyle::DoubleQuoted => format!("\"{}\"", pattern.replace('\\', "\\\\").replace('"', "\\\"")),
+        ScalarStyle::Plain if !needs_quotes => pattern.to_string(),
+        _ => format!("'{}'", pattern.replace('\'', "''")),
+    }
+}
@@ -0,0 +1,337 @@
+// Reads the paths tracked in a git index file, versions 2 to 4, without a git binary.
+// See https://git-scm.com/docs/index-format
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const SHA1_LEN: usize = 20;
+const SHA256_LEN: usize = 32;
+
+// Fixed-size part of an entry before the hash: ctime, mtime, dev, ino, mode, uid, gid and size
+const STAT_LEN: usize = 40;
+
+const EXTENDED_FLAG: u16 = 0x4000;
+const NAME_LENGTH_MASK: u16 = 0x0fff;
+const DIRECTORY_MODE: u32 = 0o040000;
+const OBJECT_TYPE_MASK: u32 = 0o170000;
+
+/// Lists the files tracked in the repository at `repo_root`, as recorded in its index.
+///
+/// Worktrees and submodules whose `.git` is a `gitdir:` file are followed, and SHA-256 repositories
+/// are recognised from their config. Paths that are not valid UTF-8 are converted lossily, see
+/// [`read_git_index_bytes`] for the paths as they are.
+pub fn read_git_index(repo_root: &Path) -> io::Result<Vec<String>> {
+    Ok(read_git_index_bytes(repo_root)?.iter().map(|path| String::from_utf8_lossy(path).into_owned()).collect())
+}
+
+/// Like [`read_git_index`], with the paths as the bytes git stores, to be matched with
+/// [`Glob::is_match_bytes`](crate::Glob::is_match_bytes).
+///
+/// A split index is read together with the shared index its `link` extension names. A sparse index
+/// is an error, as its directory entries don't list the files below them; `git sparse-checkout
+/// init --no-sparse-index` expands it.
+pub fn read_git_index_bytes(repo_root: &Path) -> io::Result<Vec<Vec<u8>>> {
+    let git_dir = find_git_dir(repo_root)?;
+    let hash_len = if uses_sha256(&git_dir) { SHA256_LEN } else { SHA1_LEN };
+
+    let index = parse_index(&fs::read(git_dir.join("index"))?, hash_len)?;
+    let entries = match index.link {
+        Some(link) => {
+            // The shared index is kept next to the index that links to it
+            let hash: String = link.shared_index.iter().map(|byte| format!("{:02x}", byte)).collect();
+            let shared = parse_index(&fs::read(git_dir.join(format!("sharedindex.{}", hash)))?, hash_len)?;
+            if shared.link.is_some() {
+                return Err(invalid_data("shared index links to another shared index"));
+            }
+            merge_split_index(shared.entries, index.entries, &link)?
+        }
+        None => index.entries,
+    };
+
+    tracked_paths(entries)
+}
+
+/// Lists the paths in the content of an index file, in index order and without duplicates.
+///
+/// `hash_len` is 20 for SHA-1 repositories and 32 for SHA-256 ones. Conflicted paths, which
+/// have an entry per stage, are listed once. A split index is an error, as most of its entries are
+/// in the shared index that [`read_git_index`] reads along with it, and so is a sparse index with
+/// directory entries, which stand for files it doesn't list. Paths that are not valid UTF-8 are
+/// converted lossily.
+pub fn parse_git_index(data: &[u8], hash_len: usize) -> io::Result<Vec<String>> {
+    Ok(parse_git_index_bytes(data, hash_len)?.iter().map(|path| String::from_utf8_lossy(path).into_owned()).collect())
+}
+
+/// Like [`parse_git_index`], with the paths as they are stored.
+pub fn parse_git_index_bytes(data: &[u8], hash_len: usize) -> io::Result<Vec<Vec<u8>>> {
+    let index = parse_index(data, hash_len)?;
+    if index.link.is_some() {
+        return Err(invalid_data("split index, whose shared index has to be read as well"));
+    }
+    tracked_paths(index.entries)
+}
+
+struct Index {
+    entries: Vec<Entry>,
+    link: Option<Link>,
+}
+
+struct Entry {
+    name: Vec<u8>,
+    mode: u32,
+}
+
+// The `link` extension of a split index: which entries of the shared index it drops, and how many
+// of its own entries replace shared ones. Replacing entries keep the shared entry's path.
+struct Link {
+    shared_index: Vec<u8>,
+    deleted: Vec<usize>,
+    replaced_count: usize,
+}
+
+fn parse_index(data: &[u8], hash_len: usize) -> io::Result<Index> {
+    let mut reader = Reader { data, pos: 0 };
+
+    if reader.take(4)? != b"DIRC" {
+        return Err(invalid_data("not a git index file"));
+    }
+    let version = reader.u32()?;
+    if !(2..=4).contains(&version) {
+        return Err(invalid_data(&format!("unsupported index version {}", version)));
+    }
+    let entry_count = reader.u32()?;
+
+    let mut entries = Vec::new();
+    let mut previous_name: Vec<u8> = Vec::new();
+
+    for _ in 0..entry_count {
+        let entry_start = reader.pos;
+
+        let stat = reader.take(STAT_LEN)?;
+        let mode = u32::from_be_bytes([stat[24], stat[25], stat[26], stat[27]]);
+        reader.take(hash_len)?;
+
+        let flags = reader.u16()?;
+        if flags & EXTENDED_FLAG != 0 {
+            if version < 3 {
+                return Err(invalid_data("extended entry flags in a version 2 index"));
+            }
+            reader.u16()?;
+        }
+
+        let name = if version == 4 {
+            // The name replaces the end of the previous one: strip N bytes, then append the rest
+            let strip = reader.varint()?;
+            if strip > previous_name.len() {
+                return Err(invalid_data("entry name strips more than the previous name"));
+            }
+            let mut name = previous_name[..previous_name.len() - strip].to_vec();
+            name.extend_from_slice(reader.until_nul()?);
+            name
+        } else {
+            let name_length = (flags & NAME_LENGTH_MASK) as usize;
+            let name = if name_length < NAME_LENGTH_MASK as usize {
+                let name = reader.take(name_length)?.to_vec();
+                reader.take(1)?; // NUL terminator
+                name
+            } else {
+                // Names of 0xFFF bytes or more only have their terminator to go by
+                reader.until_nul()?.to_vec()
+            };
+
+            // Entries are padded with NULs to a multiple of eight bytes
+            let entry_len = reader.pos - entry_start;
+            let padding = (8 - entry_len % 8) % 8;
+            reader.take(padding)?;
+            name
+        };
+
+        previous_name = name.clone();
+        entries.push(Entry { name, mode });
+    }
+
+    // Extensions follow the entries, up to the checksum of the whole file
+    let mut link = None;
+    while data.len() - reader.pos > hash_len {
+        let signature = reader.take(4)?;
+        let size = reader.u32()? as usize;
+        let mut extension = Reader { data: reader.take(size)?, pos: 0 };
+        if signature != b"link" {
+            continue;
+        }
+
+        let shared_index = extension.take(hash_len)?.to_vec();
+        // The bitmaps are left out when the split index has no deletions or replacements
+        let (deleted, replaced) = if extension.pos < extension.data.len() {
+            (ewah_bits(&mut extension)?, ewah_bits(&mut extension)?)
+        } else {
+            (vec![], vec![])
+        };
+        // A null hash means that there is no shared index
+        if shared_index.iter().any(|byte| *byte != 0) {
+            link = Some(Link { shared_index, deleted, replaced_count: replaced.len() });
+        }
+    }
+
+    Ok(Index { entries, link })
+}
+
+// The entries of the shared index that the split index keeps, then those it adds, in path order
+fn merge_split_index(shared: Vec<Entry>, split: Vec<Entry>, link: &Link) -> io::Result<Vec<Entry>> {
+    if link.replaced_count > split.len() {
+        return Err(invalid_data("split index replaces more entries than it has"));
+    }
+
+    let mut entries: Vec<Entry> = shared
+        .into_iter()
+        .enumerate()
+        .filter(|(idx, _)| link.deleted.binary_search(idx).is_err())
+        .map(|(_, entry)| entry)
+        .collect();
+    entries.extend(split.into_iter().skip(link.replaced_count));
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(entries)
+}
+
+fn tracked_paths(entries: Vec<Entry>) -> io::Result<Vec<Vec<u8>>> {
+    let mut paths: Vec<Vec<u8>> = Vec::new();
+    for entry in entries {
+        if entry.mode & OBJECT_TYPE_MASK == DIRECTORY_MODE {
+            return Err(invalid_data(&format!(
+                "sparse index, whose entry for `{}` stands for the files below it",
+                String::from_utf8_lossy(&entry.name)
+            )));
+        }
+        // Stages of a conflicted path are adjacent in the index
+        if paths.last() != Some(&entry.name) {
+            paths.push(entry.name);
+        }
+    }
+    Ok(paths)
+}
+
+// The positions of the set bits of an EWAH bitmap, in increasing order. After the bit and word
+// counts, each run-length word gives a number of words that are all zeros or all ones and the
+// number of literal words that follow it.
+fn ewah_bits(reader: &mut Reader) -> io::Result<Vec<usize>> {
+    let bit_count = reader.u32()? as usize;
+    let word_count = reader.u32()?;
+
+    let mut bits = Vec::new();
+    let mut pos = 0usize;
+    let mut literal_words = 0;
+    for _ in 0..word_count {
+        let word = reader.u64()?;
+        if literal_words > 0 {
+            bits.extend((0..64).filter(|bit| word >> bit & 1 == 1).map(|bit| pos + bit));
+            pos = pos.saturating_add(64);
+            literal_words -= 1;
+        } else {
+            let run_end = pos.saturating_add(((word >> 1) & 0xffff_ffff) as usize * 64);
+            if word & 1 == 1 {
+                bits.extend(pos..run_end.min(bit_count));
+            }
+            pos = run_end;
+            literal_words = word >> 33;
+        }
+    }
+    reader.u32()?; // position of the last run-length word
+
+    bits.retain(|bit| *bit < bit_count);
+    Ok(bits)
+}
+
+fn find_git_dir(repo_root: &Path) -> io::Result<PathBuf> {
+    let dot_git = repo_root.join(".git");
+    if dot_git.is_dir() {
+        return Ok(dot_git);
+    }
+
+    let content = fs::read_to_string(&dot_git)?;
+    let git_dir = content
+        .lines()
+        .find_map(|line| line.strip_prefix("gitdir:"))
+        .map(|git_dir| git_dir.trim())
+        .ok_or_else(|| invalid_data(".git file has no gitdir line"))?;
+
+    Ok(repo_root.join(git_dir))
+}
+
+// A worktree's git directory keeps its config in the main repository, found through "commondir"
+fn uses_sha256(git_dir: &Path) -> bool {
+    let common_dir = match fs::read_to_string(git_dir.join("commondir")) {
+        Ok(common_dir) => git_dir.join(common_dir.trim()),
+        Err(_) => git_dir.to_path_buf(),
+    };
+
+    fs::read_to_string(common_dir.join("config")).is_ok_and(|config| {
+        config.lines().any(|line| {
+            let line = line.trim().to_ascii_lowercase();
+            line.starts_with("objectformat") && line.ends_with("sha256")
+        })
+    })
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        let end = self.pos.checked_add(len).filter(|end| *end <= self.data.len());
+        let end = end.ok_or_else(|| invalid_data("index file is truncated"))?;
+
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> io::Result<u16> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn u64(&mut self) -> io::Result<u64> {
+        let bytes = self.take(8)?;
+        Ok(u64::from_be_bytes(bytes.try_into().expect("eight bytes were taken")))
+    }
+
+    // git's offset encoding, where each continuation byte also adds one
+    fn varint(&mut self) -> io::Result<usize> {
+        let mut byte = self.take(1)?[0];
+        let mut value = (byte & 0x7f) as usize;
+
+        while byte & 0x80 != 0 {
+            byte = self.take(1)?[0];
+            value = value
+                .checked_add(1)
+                .and_then(|value| value.checked_mul(128))
+                .map(|value| value + (byte & 0x7f) as usize)
+                .ok_or_else(|| invalid_data("entry name prefix length overflows"))?;
+        }
+
+        Ok(value)
+    }
+
+    fn until_nul(&mut self) -> io::Result<&'a [u8]> {
+        let len = self.data[self.pos..]
+            .iter()
+            .position(|byte| *byte == 0)
+            .ok_or_else(|| invalid_data("entry name is not terminated"))?;
+
+        let bytes = self.take(len)?;
+        self.pos += 1;
+        Ok(bytes)
+    }
+}
@@ -0,0 +1,147 @@
+// .gitignore files, following gitignore(5): ordered rules, `!` re-inclusion, and no way to
+// re-include a file once one of its parent directories is excluded.
+
+use crate::wildcard::{gitignore_style_segments, Syntax};
+use crate::{decode, match_segments, Dialect, Glob, PathChar, Pattern};
+
+const GITIGNORE_SYNTAX: Syntax =
+    Syntax { classes: true, escapes: true, braces: false, brace_ranges: false, extglobs: false };
+
+// Parses a pattern for Dialect::Gitignore, where a matched directory covers everything inside it
+pub(crate) fn parse_pattern(pattern: &str) -> Vec<(Pattern, bool)> {
+    let (body, is_negation, offset) = split_negation(pattern);
+    let segments = gitignore_style_segments(body, offset, GITIGNORE_SYNTAX, true);
+
+    vec![(Pattern { segments }, is_negation)]
+}
+
+// "\!" escapes a literal leading ! and is left for the wildcard parser to unescape
+fn split_negation(pattern: &str) -> (&str, bool, usize) {
+    match pattern.strip_prefix('!') {
+        Some(rest) => (rest, true, 1),
+        None => (pattern, false, 0),
+    }
+}
+
+/// The rules of one or more .gitignore files, in precedence order.
+#[derive(Debug, Clone, Default)]
+pub struct Gitignore {
+    rules: Vec<IgnoreRule>,
+}
+
+#[derive(Debug, Clone)]
+pub struct IgnoreRule {
+    pub glob: Glob,
+    /// Directory containing the .gitignore file, relative to the repository root ("" for the root)
+    pub base: String,
+    /// 1-based line number in its file
+    pub line: usize,
+    // The pattern without the trailing slash, matching the path itself but nothing inside it
+    exact: Pattern,
+    dir_only: bool,
+}
+
+impl Gitignore {
+    pub fn new() -> Gitignore {
+        Gitignore::default()
+    }
+
+    /// Parses a .gitignore at the repository root.
+    pub fn parse(content: &str) -> Gitignore {
+        let mut gitignore = Gitignore::new();
+        gitignore.add_file("", content);
+        gitignore
+    }
+
+    /// Adds the rules of a .gitignore in `base`, which take precedence over the ones added before,
+    /// so files should be added from the root downwards.
+    pub fn add_file(&mut self, base: &str, content: &str) {
+        let base = base.trim_matches('/');
+
+        for (idx, raw_line) in content.lines().enumerate() {
+            let pattern = trim_trailing_spaces(raw_line);
+            if pattern.is_empty() || pattern.starts_with('#') {
+                continue;
+            }
+
+            let (body, _, offset) = split_negation(pattern);
+            let (body, dir_only) = match body.strip_suffix('/') {
+                Some(rest) => (rest, true),
+                None => (body, false),
+            };
+            // A pattern like "/" has nothing left to match
+            if body.is_empty() {
+                continue;
+            }
+            let exact = Pattern { segments: gitignore_style_segments(body, offset, GITIGNORE_SYNTAX, false) };
+
+            self.rules.push(IgnoreRule {
+                glob: Glob::with_dialect(pattern, Dialect::Gitignore),
+                base: base.to_string(),
+                line: idx + 1,
+                exact,
+                dir_only,
+            });
+        }
+    }
+
+    pub fn rules(&self) -> &[IgnoreRule] {
+        &self.rules
+    }
+
+    /// Whether a file is ignored, either by a rule matching it or by one of its parent directories
+    /// being ignored.
+    pub fn is_ignored(&self, path: &str) -> bool {
+        let parts: Vec<Vec<PathChar>> =
+            path.split('/').filter(|part| !part.is_empty()).map(|part| decode(part.as_bytes())).collect();
+
+        // Once a directory is excluded git never looks inside it, so no rule can re-include its files
+        for depth in 1..parts.len() {
+            if self.decide(&parts[..depth], true) == Some(true) {
+                return true;
+            }
+        }
+
+        self.decide(&parts, false) == Some(true)
+    }
+
+    // The last rule matching the path decides: Some(true) ignored, Some(false) re-included
+    fn decide(&self, parts: &[Vec<PathChar>], is_dir: bool) -> Option<bool> {
+        self.rules.iter().rev().find_map(|rule| {
+            if rule.dir_only && !is_dir {
+                return None;
+            }
+
+            let relative = relative_to(&rule.base, parts)?;
+            if match_segments(&rule.exact.segments, relative, 0, 0, Dialect::Gitignore, rule.glob.case()) {
+                Some(!rule.glob.is_negation())
+            } else {
+                None
+            }
+        })
+    }
+}
+
+fn relative_to<'a>(base: &str, parts: &'a [Vec<PathChar>]) -> Option<&'a [Vec<PathChar>]> {
+    if base.is_empty() {
+        return Some(parts);
+    }
+
+    let base_parts: Vec<&str> = base.split('/').collect();
+    let in_base =
+        base_parts.iter().zip(parts).all(|(base, part)| part.iter().copied().eq(base.chars().map(PathChar::Char)));
+    if parts.len() > base_parts.len() && in_base {
+        Some(&parts[base_parts.len()..])
+    } else {
+        None
+    }
+}
+
+// Trailing spaces are ignored unless escaped with a backslash
+fn trim_trailing_spaces(line: &str) -> &str {
+    let mut end = line.len();
+    while line[..end].ends_with(' ') && !line[..end - 1].ends_with('\\') {
+        end -= 1;
+    }
+    &line[..end]
+}
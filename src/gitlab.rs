@@ -0,0 +1,125 @@
+// GitLab CI `rules:changes` and `only/except:changes`, which GitLab evaluates with Ruby's
+// `File.fnmatch?(pattern, path, File::FNM_PATHNAME | File::FNM_DOTMATCH | File::FNM_EXTGLOB)`.
+// See https://docs.gitlab.com/ci/yaml/#ruleschanges
+
+use crate::wildcard::{expand_braces, parse, split_segments, Node, NodeKind, Syntax};
+use crate::{yaml, Dialect, Glob, Pattern, Segment, SourcePattern};
+
+// FNM_EXTGLOB only enables braces in Ruby, and those don't support "{1..3}" sequences
+const GITLAB_SYNTAX: Syntax =
+    Syntax { classes: true, escapes: true, braces: true, brace_ranges: false, extglobs: false };
+
+// Parses a pattern for Dialect::GitLab. There is no negation, so a leading ! is literal.
+pub(crate) fn parse_pattern(pattern: &str) -> Vec<(Pattern, bool)> {
+    expand_braces(&parse(pattern, 0, GITLAB_SYNTAX))
+        .iter()
+        .map(|variant| {
+            let mut segments = split_segments(variant);
+
+            // Only "**/" spans directories; a trailing ** is the same as *
+            if matches!(segments.last(), Some(Segment::DoubleStar)) {
+                let last = variant.last().map_or(0, |node| node.offset);
+                segments.pop();
+                segments.push(Segment::Wildcard(vec![Node { kind: NodeKind::Star { double: true }, offset: last }]));
+            }
+
+            (Pattern { segments }, false)
+        })
+        .collect()
+}
+
+/// A `changes:` entry of a .gitlab-ci.yml job.
+#[derive(Debug, Clone)]
+pub struct GitlabChanges {
+    /// The job name, or "workflow" for the pipeline-level rules
+    pub job: String,
+    /// Where the entry is within the job: "rules[0]", "only" or "except"
+    pub context: String,
+    pub patterns: Vec<SourcePattern>,
+}
+
+impl GitlabChanges {
+    /// Whether any of the changed paths matches any of the patterns, which is when GitLab
+    /// considers the entry satisfied.
+    pub fn is_triggered_by(&self, changed_paths: &[&str]) -> bool {
+        changed_paths.iter().any(|path| self.patterns.iter().any(|pattern| pattern.glob.is_match(path)))
+    }
+}
+
+// Top-level keys that configure the pipeline rather than define a job. `pages` is left out, as it
+// is the job that deploys GitLab Pages.
+const RESERVED_KEYWORDS: &[&str] = &[
+    "after_script",
+    "before_script",
+    "cache",
+    "default",
+    "image",
+    "include",
+    "services",
+    "stages",
+    "variables",
+    "workflow",
+];
+
+/// Extracts every `changes:` entry from the content of a .gitlab-ci.yml file.
+///
+/// Hidden jobs, whose name starts with `.`, never run on their own and are skipped; the jobs that
+/// `extends:` them are not resolved.
+pub fn parse_gitlab_ci(content: &str) -> Vec<GitlabChanges> {
+    let document = yaml::parse(content);
+    let mut changes = Vec::new();
+
+    for (key, value) in document.entries() {
+        if key.value == "workflow" {
+            collect_rules_changes("workflow", value, &mut changes);
+            continue;
+        }
+        if RESERVED_KEYWORDS.contains(&key.value.as_str()) || key.value.starts_with('.') {
+            continue;
+        }
+
+        collect_rules_changes(&key.value, value, &mut changes);
+        for context in ["only", "except"] {
+            if let Some(patterns) = value.get(context).and_then(|condition| condition.get("changes")) {
+                changes.push(GitlabChanges {
+                    job: key.value.clone(),
+                    context: context.to_string(),
+                    patterns: source_patterns(patterns),
+                });
+            }
+        }
+    }
+
+    changes
+}
+
+fn collect_rules_changes(job: &str, definition: &yaml::Node, changes: &mut Vec<GitlabChanges>) {
+    let Some(rules) = definition.get("rules") else {
+        return;
+    };
+
+    for (idx, rule) in rules.items().iter().enumerate() {
+        if let Some(patterns) = rule.get("changes") {
+            changes.push(GitlabChanges {
+                job: job.to_string(),
+                context: format!("rules[{}]", idx),
+                patterns: source_patterns(patterns),
+            });
+        }
+    }
+}
+
+// `changes:` is either a list of patterns or, since GitLab 15.2, a mapping with `paths:`
+fn source_patterns(changes: &yaml::Node) -> Vec<SourcePattern> {
+    let patterns = changes.get("paths").unwrap_or(changes);
+
+    patterns
+        .scalars()
+        .into_iter()
+        .map(|scalar| SourcePattern {
+            glob: Glob::with_dialect(&scalar.value, Dialect::GitLab),
+            line: scalar.line,
+            column: scalar.column,
+        })
+        .collect()
+}
@@ -1,36 +1,417 @@
+mod azure;
+mod cli;
+mod codeowners;
+mod coverage;
+mod diff;
+mod fix;
+mod git_index;
+mod gitignore;
+mod gitlab;
+mod json;
+mod lint;
+mod lsp;
+mod matrix;
+mod normalize;
+mod picomatch;
+mod regex;
+mod repl;
+mod unicode_tables;
+mod walk;
+mod wildcard;
+mod workflow;
+mod yaml;
+
+pub use azure::AzurePathFilter;
+pub use cli::{run_cli, JSON_SCHEMA_VERSION};
+pub use codeowners::{CodeOwners, CodeOwnersError, OwnerRule};
+pub use coverage::{find_uncovered_files, UncoveredDirectory};
+pub use diff::{diff_workflows, FilterChange};
+pub use fix::{apply_fixes, find_fixes, fix_pattern, Fix, PatternFix};
+pub use git_index::{parse_git_index, parse_git_index_bytes, read_git_index, read_git_index_bytes};
+pub use gitignore::{Gitignore, IgnoreRule};
+pub use gitlab::{parse_gitlab_ci, GitlabChanges};
+pub use lint::{
+    apply_quoting, find_dead_patterns, find_quoting_problems, find_shadowed_patterns, DeadPattern, QuotingProblem,
+    ShadowedPattern,
+};
+pub use lsp::run_lsp;
+pub use matrix::{trigger_matrix, MatrixRow, TriggerMatrix};
+pub use normalize::{Normalization, PathError};
+pub use picomatch::{github_to_picomatch, picomatch_to_github, Conversion, ConversionIssue};
+pub use repl::run_repl;
+pub use walk::{find_files, find_files_bytes};
+pub use workflow::{parse_workflow, PathDecision, Workflow, WorkflowEvent};
+
+use std::ffi::OsStr;
+use std::path::Path;
+
 pub fn match_path(pattern: &str, paths: &[&str]) -> bool {
     if paths.is_empty() {
         return false;
     }
 
     // Parse the single pattern, expanding optionals into multiple variants
-    let parsed_patterns: Vec<(Pattern, bool)> = parse_pattern(pattern);
+    let glob = Glob::new(pattern);
+
+    // A lone negation pattern has nothing to exclude from, so it never includes a path
+    if glob.is_negation {
+        return false;
+    }
 
     // Check if any path matches the pattern
-    for path in paths {
-        let path_segments = if path.is_empty() { vec![] } else { path.split('/').collect() };
+    paths.iter().any(|path| glob.is_match(path))
+}
 
-        // Process pre-parsed pattern variants sequentially - each variant can override previous results
-        let mut matched = false;
+/// The pattern syntax and matching rules a [`Glob`] follows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Dialect {
+    /// GitHub Actions `paths`, `branches` and `tags` filters.
+    #[default]
+    GitHub,
+    /// picomatch, as used by dorny/paths-filter: braces, `?` as any single character, extglobs
+    /// and backslash escapes. Unless `dot` is set, wildcards do not match a leading `.` in a
+    /// segment, the same as picomatch's `dot` option.
+    Picomatch { dot: bool },
+    /// CODEOWNERS files: unanchored unless the pattern contains a slash, a trailing `/` for
+    /// directories, and a matched directory covering everything inside it.
+    Codeowners,
+    /// .gitignore files: the CODEOWNERS anchoring rules plus character classes, backslash escapes
+    /// and `!` negation. A single pattern can't tell whether a path is a directory, so paths are
+    /// treated as files; see [`Gitignore`] for the rules that span several patterns.
+    Gitignore,
+    /// GitLab CI `changes:` entries, matched like Ruby's `File.fnmatch?` with `FNM_PATHNAME`,
+    /// `FNM_DOTMATCH` and `FNM_EXTGLOB`: braces, `?` as any single character, and `**/` as the only
+    /// way to span directories.
+    GitLab,
+    /// Azure Pipelines path filters: anchored at the root with an optional leading `/`, `*` and
+    /// `?` wildcards, and a path covering everything below it. See [`AzurePathFilter`] for how the
+    /// include and exclude lists combine.
+    Azure,
+}
 
-        for (parsed_pattern, is_negation) in &parsed_patterns {
-            if match_segments(&parsed_pattern.segments, &path_segments, 0, 0) {
-                if *is_negation {
-                    matched = false; // Negation pattern matched - exclude the path
-                } else {
-                    matched = true; // Positive pattern matched - include the path
-                }
-            }
+/// How a [`Glob`] compares letters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Case {
+    /// Letters only match themselves, as in GitHub filters.
+    #[default]
+    Sensitive,
+    /// ASCII letters also match their other case, for paths from case-insensitive filesystems.
+    AsciiInsensitive,
+    /// Letters in any script also match their other case, as far as it is a single character: `ß`
+    /// doesn't match `ss`.
+    UnicodeInsensitive,
+}
+
+impl Case {
+    pub(crate) fn char_eq(self, a: char, b: char) -> bool {
+        match self {
+            Case::Sensitive => a == b,
+            Case::AsciiInsensitive => a.eq_ignore_ascii_case(&b),
+            // Comparing both cases catches letters like the Kelvin sign, whose lowercase is `k`
+            // but which `K` doesn't lowercase to
+            Case::UnicodeInsensitive => a == b || lowercase(a) == lowercase(b) || uppercase(a) == uppercase(b),
         }
+    }
+
+    fn str_eq(self, text: &[PathChar], literal: &str) -> bool {
+        text.len() == literal.chars().count() && text.iter().zip(literal.chars()).all(|(t, c)| t.matches(c, self))
+    }
+
+    fn ends_with(self, text: &[PathChar], suffix: &str) -> bool {
+        let skip = text.len().checked_sub(suffix.chars().count());
+        skip.is_some_and(|skip| text[skip..].iter().zip(suffix.chars()).all(|(t, c)| t.matches(c, self)))
+    }
+
+    // The character in each of the cases it matches, for checking it against a bracket class
+    pub(crate) fn variants(self, c: char) -> impl Iterator<Item = char> {
+        let (lower, upper) = match self {
+            Case::Sensitive => (c, c),
+            Case::AsciiInsensitive => (c.to_ascii_lowercase(), c.to_ascii_uppercase()),
+            Case::UnicodeInsensitive => (lowercase(c), uppercase(c)),
+        };
+        [c, lower, upper].into_iter()
+    }
+}
+
+// The lowercase of a character, if it is a single character
+fn lowercase(c: char) -> char {
+    let mut lower = c.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(lower), None) => lower,
+        _ => c,
+    }
+}
+
+fn uppercase(c: char) -> char {
+    let mut upper = c.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(upper), None) => upper,
+        _ => c,
+    }
+}
+
+/// A character of a path being matched, or a byte of it that isn't valid UTF-8. A byte never matches
+/// a character of the pattern, but counts as a character for wildcards and negated classes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PathChar {
+    Char(char),
+    Byte(u8),
+}
+
+impl PathChar {
+    pub(crate) fn matches(self, c: char, case: Case) -> bool {
+        matches!(self, PathChar::Char(t) if case.char_eq(t, c))
+    }
+
+    // Whether the character, in any of the cases it matches, is in the class
+    pub(crate) fn in_class(self, case: Case, contains: impl Fn(char) -> bool) -> bool {
+        matches!(self, PathChar::Char(t) if case.variants(t).any(contains))
+    }
+}
+
+// Splits a path into its decoded segments
+fn path_segments(path: &[u8]) -> Vec<Vec<PathChar>> {
+    path.split(|byte| *byte == b'/').map(decode).collect()
+}
+
+pub(crate) fn decode(bytes: &[u8]) -> Vec<PathChar> {
+    let mut chars = Vec::with_capacity(bytes.len());
+    for chunk in bytes.utf8_chunks() {
+        chars.extend(chunk.valid().chars().map(PathChar::Char));
+        chars.extend(chunk.invalid().iter().map(|byte| PathChar::Byte(*byte)));
+    }
+    chars
+}
+
+impl Dialect {
+    fn hides_dotfiles(&self) -> bool {
+        matches!(self, Dialect::Picomatch { dot: false })
+    }
+}
+
+/// A single filter pattern, parsed once so it can be matched against many paths.
+///
+/// A leading `!` is recorded as [`Glob::is_negation`]; [`Glob::is_match`] only answers whether the
+/// rest of the pattern matches, leaving it to the caller to apply the negation in sequence.
+#[derive(Debug, Clone)]
+pub struct Glob {
+    pattern: String,
+    dialect: Dialect,
+    variants: Vec<Pattern>,
+    is_negation: bool,
+    errors: Vec<PatternError>,
+    case: Case,
+    normalization: Normalization,
+}
 
-        // If this path matched, return true immediately
-        if matched {
-            return true;
+impl Glob {
+    pub fn new(pattern: &str) -> Glob {
+        Glob::with_dialect(pattern, Dialect::GitHub)
+    }
+
+    pub fn with_dialect(pattern: &str, dialect: Dialect) -> Glob {
+        let mut errors = Vec::new();
+        let parsed_patterns = match dialect {
+            Dialect::GitHub => parse_pattern(pattern, &mut errors),
+            Dialect::Picomatch { .. } => picomatch::parse_pattern(pattern),
+            Dialect::Codeowners => codeowners::parse_pattern(pattern),
+            Dialect::Gitignore => gitignore::parse_pattern(pattern),
+            Dialect::GitLab => gitlab::parse_pattern(pattern),
+            Dialect::Azure => azure::parse_pattern(pattern),
+        };
+        let is_negation = parsed_patterns.first().is_some_and(|(_, is_negation)| *is_negation);
+        let variants = parsed_patterns.into_iter().map(|(parsed_pattern, _)| parsed_pattern).collect();
+
+        Glob {
+            pattern: pattern.to_string(),
+            dialect,
+            variants,
+            is_negation,
+            errors,
+            case: Case::Sensitive,
+            normalization: Normalization::Off,
         }
     }
 
-    // No paths matched the pattern
-    false
+    /// The same pattern, comparing letters as given. Bracket classes like `[A-C]` follow the same
+    /// rule, so `[A-C]` matches `b` unless the case is [`Case::Sensitive`].
+    pub fn with_case(mut self, case: Case) -> Glob {
+        self.case = case;
+        self
+    }
+
+    /// The same pattern, applying the normalisation to each path before matching it. A path that
+    /// normalisation rejects matches nothing; [`Glob::try_match`] reports why.
+    pub fn with_normalization(mut self, normalization: Normalization) -> Glob {
+        self.normalization = normalization;
+        self
+    }
+
+    /// The pattern as it was written, including any leading `!`.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn dialect(&self) -> Dialect {
+        self.dialect
+    }
+
+    pub fn case(&self) -> Case {
+        self.case
+    }
+
+    pub fn normalization(&self) -> Normalization {
+        self.normalization
+    }
+
+    pub fn is_negation(&self) -> bool {
+        self.is_negation
+    }
+
+    /// The parts of the pattern that keep it from ever matching, such as an unclosed `[`. Only
+    /// GitHub patterns are checked.
+    pub fn errors(&self) -> &[PatternError] {
+        &self.errors
+    }
+
+    pub fn is_match(&self, path: &str) -> bool {
+        self.try_match(path).unwrap_or(false)
+    }
+
+    /// Like [`Glob::is_match`], with an error for a path that normalisation rejects.
+    pub fn try_match(&self, path: &str) -> Result<bool, PathError> {
+        self.try_match_bytes(path.as_bytes())
+    }
+
+    /// Like [`Glob::is_match`], for a path that may not be valid UTF-8, such as one read from the git
+    /// index. Bytes that aren't valid UTF-8 only match wildcards.
+    pub fn is_match_bytes(&self, path: &[u8]) -> bool {
+        self.try_match_bytes(path).unwrap_or(false)
+    }
+
+    pub fn try_match_bytes(&self, path: &[u8]) -> Result<bool, PathError> {
+        let path = self.normalization.apply_bytes(path)?;
+        let path_segments = if path.is_empty() { vec![] } else { path_segments(&path) };
+
+        Ok(self
+            .variants
+            .iter()
+            .any(|variant| match_segments(&variant.segments, &path_segments, 0, 0, self.dialect, self.case)))
+    }
+
+    /// Matches the encoded bytes of the string: the bytes themselves on Unix, and WTF-8 on Windows,
+    /// where `\\` separators are only understood with [`Normalization::Normalize`].
+    pub fn is_match_os(&self, path: &OsStr) -> bool {
+        self.is_match_bytes(path.as_encoded_bytes())
+    }
+
+    pub fn is_match_path(&self, path: &Path) -> bool {
+        self.is_match_os(path.as_os_str())
+    }
+
+    /// Whether any path below the directory could match, judging from the pattern alone.
+    ///
+    /// A `false` answer is definite, so walkers and watchers can skip the whole directory; `true`
+    /// only means a match can't be ruled out, e.g. whenever a `**` is reached. The negation of a
+    /// negation pattern is not taken into account.
+    pub fn could_match_under(&self, dir_prefix: &str) -> bool {
+        self.could_match_under_bytes(dir_prefix.as_bytes())
+    }
+
+    /// Like [`Glob::could_match_under`], for a directory that may not be valid UTF-8.
+    pub fn could_match_under_bytes(&self, dir_prefix: &[u8]) -> bool {
+        // The prefix is only cleaned up even in strict mode, as `docs/` has paths like `docs/a.md`
+        // below it. Nothing below a rejected directory can match.
+        let normalization = match self.normalization {
+            Normalization::Strict => Normalization::Normalize,
+            normalization => normalization,
+        };
+        let Ok(dir_prefix) = normalization.apply_bytes(dir_prefix) else {
+            return false;
+        };
+        let dir_segments: Vec<Vec<PathChar>> =
+            dir_prefix.split(|byte| *byte == b'/').filter(|part| !part.is_empty()).map(decode).collect();
+
+        self.variants
+            .iter()
+            .any(|variant| could_match_below(&variant.segments, &dir_segments, 0, 0, self.dialect, self.case))
+    }
+}
+
+/// An ordered list of patterns, like the entries of a workflow's `paths` filter.
+///
+/// Patterns are checked in sequence and the last one matching a path decides: a negation pattern
+/// excludes the path, any other pattern includes it.
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    globs: Vec<Glob>,
+}
+
+impl Filter {
+    pub fn new(patterns: &[&str]) -> Filter {
+        Filter::from_globs(patterns.iter().map(|pattern| Glob::new(pattern)).collect())
+    }
+
+    pub fn from_globs(globs: Vec<Glob>) -> Filter {
+        Filter { globs }
+    }
+
+    pub fn globs(&self) -> &[Glob] {
+        &self.globs
+    }
+
+    pub fn is_match(&self, path: &str) -> bool {
+        self.deciding_glob(path).is_some_and(|idx| !self.globs[idx].is_negation())
+    }
+
+    /// Like [`Filter::is_match`], for a path that may not be valid UTF-8.
+    pub fn is_match_bytes(&self, path: &[u8]) -> bool {
+        let deciding_glob = self.globs.iter().rposition(|glob| glob.is_match_bytes(path));
+        deciding_glob.is_some_and(|idx| !self.globs[idx].is_negation())
+    }
+
+    /// Like [`Filter::is_match`], for the encoded bytes of the string as [`Glob::is_match_os`].
+    pub fn is_match_os(&self, path: &OsStr) -> bool {
+        self.is_match_bytes(path.as_encoded_bytes())
+    }
+
+    pub fn is_match_path(&self, path: &Path) -> bool {
+        self.is_match_os(path.as_os_str())
+    }
+
+    /// Whether any path below the directory could be included. Negation patterns only ever exclude
+    /// paths, so they are not considered.
+    pub fn could_match_under(&self, dir_prefix: &str) -> bool {
+        self.could_match_under_bytes(dir_prefix.as_bytes())
+    }
+
+    pub fn could_match_under_bytes(&self, dir_prefix: &[u8]) -> bool {
+        self.globs.iter().any(|glob| !glob.is_negation() && glob.could_match_under_bytes(dir_prefix))
+    }
+
+    /// The index of the last pattern matching the path, which decides whether it is included.
+    pub fn deciding_glob(&self, path: &str) -> Option<usize> {
+        self.globs.iter().rposition(|glob| glob.is_match(path))
+    }
+}
+
+/// A pattern read from a configuration file, along with where it was written.
+#[derive(Debug, Clone)]
+pub struct SourcePattern {
+    pub glob: Glob,
+    /// 1-based position of the pattern's first character, including any opening quote
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A construct that makes a pattern match nothing, with the characters it spans.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternError {
+    /// Character offset of the construct in the pattern, including any leading `!`
+    pub offset: usize,
+    /// Number of characters the construct spans
+    pub len: usize,
+    pub message: String,
 }
 
 #[derive(Debug, Clone)]
@@ -40,32 +421,41 @@ struct Pattern {
 
 #[derive(Debug, Clone)]
 enum Segment {
-    Literal(String),              // "docs", "file.txt"
-    Pattern(String),              // "*.js", "*.jsx+", "[CB]at", etc.
-    DoubleStar,                   // "**"
-    DoubleStarWithSuffix(String), // "**.js"
+    Literal(String),               // "docs", "file.txt"
+    Pattern(String),               // "*.js", "*.jsx+", "[CB]at", etc.
+    DoubleStar,                    // "**"
+    DoubleStarWithSuffix(String),  // "**.js"
+    Wildcard(Vec<wildcard::Node>), // "*.@(js|ts)", "[!.]*" in the other dialects
 }
 
-fn parse_pattern(pattern: &str) -> Vec<(Pattern, bool)> {
-    let (actual_pattern, is_negation) = if pattern.starts_with('!') { (&pattern[1..], true) } else { (pattern, false) };
+fn parse_pattern(pattern: &str, errors: &mut Vec<PatternError>) -> Vec<(Pattern, bool)> {
+    let (actual_pattern, is_negation) = match pattern.strip_prefix('!') {
+        Some(rest) => (rest, true),
+        None => (pattern, false),
+    };
+
+    // Every character keeps its offset in the pattern as written, so errors point at the source
+    let chars: Vec<(usize, char)> =
+        actual_pattern.chars().enumerate().map(|(idx, c)| (idx + is_negation as usize, c)).collect();
 
     // Expand optionals into multiple patterns
-    expand_optionals(actual_pattern)
+    expand_optional_chars(&chars)
         .into_iter()
         .map(|expanded_pattern| {
-            let parts: Vec<&str> = expanded_pattern.split('/').collect();
             let mut segments = Vec::new();
 
-            for part in parts {
+            for part_chars in expanded_pattern.split(|(_, c)| *c == '/') {
+                check_segment(part_chars, errors);
+
+                let part: String = part_chars.iter().map(|(_, c)| c).collect();
                 if part == "**" {
                     segments.push(Segment::DoubleStar);
-                } else if part.starts_with("**") {
-                    let suffix = &part[2..];
+                } else if let Some(suffix) = part.strip_prefix("**") {
                     segments.push(Segment::DoubleStarWithSuffix(suffix.to_string()));
                 } else if part.contains('*') || part.contains('+') || part.contains('[') {
-                    segments.push(Segment::Pattern(part.to_string()));
+                    segments.push(Segment::Pattern(part));
                 } else {
-                    segments.push(Segment::Literal(part.to_string()));
+                    segments.push(Segment::Literal(part));
                 }
             }
 
@@ -74,32 +464,80 @@ fn parse_pattern(pattern: &str) -> Vec<(Pattern, bool)> {
         .collect()
 }
 
+// Records the constructs in a segment that can never match. Variants share most of their
+// characters, so each error is only recorded once.
+fn check_segment(part: &[(usize, char)], errors: &mut Vec<PatternError>) {
+    let mut push = |offset: usize, len: usize, message: &str| {
+        if !errors.iter().any(|error| error.offset == offset) {
+            errors.push(PatternError { offset, len, message: message.to_string() });
+        }
+    };
+    let text: Vec<char> = part.iter().map(|(_, c)| *c).collect();
+
+    // Leading operators and brackets only have a meaning in the segments matched by glob_match
+    if text.starts_with(&['*', '*']) || !text.iter().any(|c| matches!(c, '*' | '+' | '[')) {
+        return;
+    }
+
+    match text.first() {
+        Some('?') => push(part[0].0, 1, "`?` makes the character before it optional, and there is none"),
+        Some('+') => push(part[0].0, 1, "`+` repeats the character before it, and there is none"),
+        _ => {}
+    }
+
+    let mut idx = 0;
+    while let Some(start) = text[idx..].iter().position(|c| *c == '[').map(|start| idx + start) {
+        let Some(len) = text[start..].iter().position(|c| *c == ']') else {
+            push(part[start].0, 1, "`[` is never closed, so the pattern can't match");
+            return;
+        };
+        if len == 1 {
+            push(part[start].0, 2, "`[]` matches no character, so the pattern can't match");
+        }
+        idx = start + len + 1;
+    }
+}
+
 fn expand_optionals(pattern: &str) -> Vec<String> {
-    if let Some(question_pos) = pattern.find('?') {
+    let chars: Vec<(usize, char)> = pattern.chars().enumerate().collect();
+
+    expand_optional_chars(&chars).iter().map(|variant| variant.iter().map(|(_, c)| c).collect()).collect()
+}
+
+// Expands optionals on characters paired with their offset in the pattern
+fn expand_optional_chars(pattern: &[(usize, char)]) -> Vec<Vec<(usize, char)>> {
+    if let Some(question_pos) = pattern.iter().position(|(_, c)| *c == '?') {
         if question_pos == 0 {
-            return vec![pattern.to_string()]; // Invalid pattern, return as-is
+            return vec![pattern.to_vec()]; // Invalid pattern, return as-is
         }
 
-        let optional_char = pattern.chars().nth(question_pos - 1).unwrap();
         let before_optional = &pattern[..question_pos - 1];
+        let optional_char = pattern[question_pos - 1];
         let after_optional = &pattern[question_pos + 1..];
 
         // Create two variants: without and with the optional character
-        let pattern_without = format!("{}{}", before_optional, after_optional);
-        let pattern_with = format!("{}{}{}", before_optional, optional_char, after_optional);
+        let pattern_without = [before_optional, after_optional].concat();
+        let pattern_with = [before_optional, &[optional_char], after_optional].concat();
 
         // Recursively expand any remaining optionals in both variants
         let mut results = Vec::new();
-        results.extend(expand_optionals(&pattern_without));
-        results.extend(expand_optionals(&pattern_with));
+        results.extend(expand_optional_chars(&pattern_without));
+        results.extend(expand_optional_chars(&pattern_with));
         results
     } else {
         // No more optionals, return the pattern as-is
-        vec![pattern.to_string()]
+        vec![pattern.to_vec()]
     }
 }
 
-fn match_segments(segments: &[Segment], path_parts: &[&str], seg_idx: usize, path_idx: usize) -> bool {
+fn match_segments(
+    segments: &[Segment],
+    path_parts: &[Vec<PathChar>],
+    seg_idx: usize,
+    path_idx: usize,
+    dialect: Dialect,
+    case: Case,
+) -> bool {
     // Base case: both exhausted
     if seg_idx >= segments.len() && path_idx >= path_parts.len() {
         return true;
@@ -111,33 +549,17 @@ fn match_segments(segments: &[Segment], path_parts: &[&str], seg_idx: usize, pat
     }
 
     match &segments[seg_idx] {
-        Segment::Literal(literal) => {
-            if path_idx >= path_parts.len() || path_parts[path_idx] != literal {
-                return false;
-            }
-            match_segments(segments, path_parts, seg_idx + 1, path_idx + 1)
-        }
-
-        Segment::Pattern(pattern) => {
-            if path_idx >= path_parts.len() {
-                return false;
-            }
-
-            if glob_match(pattern, path_parts[path_idx]) {
-                match_segments(segments, path_parts, seg_idx + 1, path_idx + 1)
-            } else {
-                false
-            }
-        }
-
         Segment::DoubleStar => {
             // Try consuming 0 or more path segments
-            if match_segments(segments, path_parts, seg_idx + 1, path_idx) {
+            if match_segments(segments, path_parts, seg_idx + 1, path_idx, dialect, case) {
                 return true;
             }
 
             for i in (path_idx + 1)..=path_parts.len() {
-                if match_segments(segments, path_parts, seg_idx + 1, i) {
+                if dialect.hides_dotfiles() && path_parts[i - 1].first() == Some(&PathChar::Char('.')) {
+                    return false; // ** does not descend into dot directories
+                }
+                if match_segments(segments, path_parts, seg_idx + 1, i, dialect, case) {
                     return true;
                 }
             }
@@ -146,28 +568,97 @@ fn match_segments(segments: &[Segment], path_parts: &[&str], seg_idx: usize, pat
 
         Segment::DoubleStarWithSuffix(suffix) => {
             for i in path_idx..path_parts.len() {
-                if path_parts[i].ends_with(suffix) {
-                    if match_segments(segments, path_parts, seg_idx + 1, i + 1) {
-                        return true;
-                    }
+                if case.ends_with(&path_parts[i], suffix)
+                    && match_segments(segments, path_parts, seg_idx + 1, i + 1, dialect, case)
+                {
+                    return true;
+                }
+            }
+            false
+        }
+
+        // Segments matching exactly one path segment
+        segment => {
+            if path_idx >= path_parts.len() || !match_single_segment(segment, &path_parts[path_idx], dialect, case) {
+                return false;
+            }
+            match_segments(segments, path_parts, seg_idx + 1, path_idx + 1, dialect, case)
+        }
+    }
+}
+
+fn match_single_segment(segment: &Segment, part: &[PathChar], dialect: Dialect, case: Case) -> bool {
+    match segment {
+        Segment::Literal(literal) => case.str_eq(part, literal),
+
+        Segment::Pattern(pattern) => glob_match(pattern, part, case),
+
+        Segment::Wildcard(nodes) => {
+            let explicit_dot =
+                matches!(nodes.first(), Some(wildcard::Node { kind: wildcard::NodeKind::Literal('.'), .. }));
+            if dialect.hides_dotfiles() && part.first() == Some(&PathChar::Char('.')) && !explicit_dot {
+                return false;
+            }
+
+            wildcard::match_nodes(nodes, part, case)
+        }
+
+        Segment::DoubleStar | Segment::DoubleStarWithSuffix(_) => unreachable!("spans several segments"),
+    }
+}
+
+// Whether the segments could match some path below the directory given by `dir_parts`
+fn could_match_below(
+    segments: &[Segment],
+    dir_parts: &[Vec<PathChar>],
+    seg_idx: usize,
+    path_idx: usize,
+    dialect: Dialect,
+    case: Case,
+) -> bool {
+    // The directory is consumed: anything left in the pattern can match the entries below it
+    if path_idx >= dir_parts.len() {
+        return seg_idx < segments.len();
+    }
+
+    if seg_idx >= segments.len() {
+        return false;
+    }
+
+    match &segments[seg_idx] {
+        Segment::DoubleStar => {
+            for i in path_idx..=dir_parts.len() {
+                if i > path_idx && dialect.hides_dotfiles() && dir_parts[i - 1].first() == Some(&PathChar::Char('.')) {
+                    return false;
+                }
+                // Absorbing the whole directory leaves ** to match whatever is below it
+                if i == dir_parts.len() || could_match_below(segments, dir_parts, seg_idx + 1, i, dialect, case) {
+                    return true;
                 }
             }
             false
         }
+
+        // Can absorb the rest of the directory and end with a file below it
+        Segment::DoubleStarWithSuffix(_) => true,
+
+        segment => {
+            match_single_segment(segment, &dir_parts[path_idx], dialect, case)
+                && could_match_below(segments, dir_parts, seg_idx + 1, path_idx + 1, dialect, case)
+        }
     }
 }
 
 // Single function that handles all glob pattern matching within a path segment
-fn glob_match(pattern: &str, text: &str) -> bool {
-    glob_match_recursive(pattern, text, 0, 0)
+fn glob_match(pattern: &str, text: &[PathChar], case: Case) -> bool {
+    glob_match_recursive(pattern, text, 0, 0, case)
 }
 
-fn glob_match_recursive(pattern: &str, text: &str, p_idx: usize, t_idx: usize) -> bool {
+fn glob_match_recursive(pattern: &str, text: &[PathChar], p_idx: usize, t_idx: usize, case: Case) -> bool {
     let p_chars: Vec<char> = pattern.chars().collect();
-    let t_chars: Vec<char> = text.chars().collect();
 
     // Base cases
-    if p_idx >= p_chars.len() && t_idx >= t_chars.len() {
+    if p_idx >= p_chars.len() && t_idx >= text.len() {
         return true; // Both exhausted
     }
     if p_idx >= p_chars.len() {
@@ -177,8 +668,8 @@ fn glob_match_recursive(pattern: &str, text: &str, p_idx: usize, t_idx: usize) -
     match p_chars[p_idx] {
         '*' => {
             // Try matching 0 or more characters
-            for i in t_idx..=t_chars.len() {
-                if glob_match_recursive(pattern, text, p_idx + 1, i) {
+            for i in t_idx..=text.len() {
+                if glob_match_recursive(pattern, text, p_idx + 1, i, case) {
                     return true;
                 }
             }
@@ -199,7 +690,7 @@ fn glob_match_recursive(pattern: &str, text: &str, p_idx: usize, t_idx: usize) -
             let mut curr_t_idx = t_idx;
 
             // Count how many times the character repeats at current position
-            while curr_t_idx < t_chars.len() && t_chars[curr_t_idx] == char_to_repeat {
+            while curr_t_idx < text.len() && text[curr_t_idx].matches(char_to_repeat, case) {
                 repeat_count += 1;
                 curr_t_idx += 1;
             }
@@ -209,11 +700,11 @@ fn glob_match_recursive(pattern: &str, text: &str, p_idx: usize, t_idx: usize) -
             }
 
             // Continue matching from where we left off
-            glob_match_recursive(pattern, text, p_idx + 1, curr_t_idx)
+            glob_match_recursive(pattern, text, p_idx + 1, curr_t_idx, case)
         }
 
         '[' => {
-            if t_idx >= t_chars.len() {
+            if t_idx >= text.len() {
                 return false;
             }
 
@@ -230,8 +721,8 @@ fn glob_match_recursive(pattern: &str, text: &str, p_idx: usize, t_idx: usize) -
             // Extract bracket content
             let bracket_content: String = p_chars[(p_idx + 1)..bracket_end].iter().collect();
 
-            if matches_bracket_content(&bracket_content, t_chars[t_idx]) {
-                glob_match_recursive(pattern, text, bracket_end + 1, t_idx + 1)
+            if text[t_idx].in_class(case, |c| matches_bracket_content(&bracket_content, c)) {
+                glob_match_recursive(pattern, text, bracket_end + 1, t_idx + 1, case)
             } else {
                 false
             }
@@ -244,15 +735,15 @@ fn glob_match_recursive(pattern: &str, text: &str, p_idx: usize, t_idx: usize) -
 
             // Optional character - try both with and without
             // Without the optional character
-            if glob_match_recursive(pattern, text, p_idx + 1, t_idx) {
+            if glob_match_recursive(pattern, text, p_idx + 1, t_idx, case) {
                 return true;
             }
 
             // With the optional character (if it matches the preceding character)
-            if t_idx < t_chars.len() {
+            if t_idx < text.len() {
                 let optional_char = p_chars[p_idx - 1];
-                if t_chars[t_idx] == optional_char {
-                    return glob_match_recursive(pattern, text, p_idx + 1, t_idx + 1);
+                if text[t_idx].matches(optional_char, case) {
+                    return glob_match_recursive(pattern, text, p_idx + 1, t_idx + 1, case);
                 }
             }
 
@@ -266,7 +757,7 @@ fn glob_match_recursive(pattern: &str, text: &str, p_idx: usize, t_idx: usize) -
                 let mut repeat_count = 0;
                 let mut curr_t_idx = t_idx;
 
-                while curr_t_idx < t_chars.len() && t_chars[curr_t_idx] == c {
+                while curr_t_idx < text.len() && text[curr_t_idx].matches(c, case) {
                     repeat_count += 1;
                     curr_t_idx += 1;
                 }
@@ -276,13 +767,13 @@ fn glob_match_recursive(pattern: &str, text: &str, p_idx: usize, t_idx: usize) -
                 }
 
                 // Skip both the character and the '+' in pattern
-                glob_match_recursive(pattern, text, p_idx + 2, curr_t_idx)
+                glob_match_recursive(pattern, text, p_idx + 2, curr_t_idx, case)
             } else {
                 // Regular literal character
-                if t_idx >= t_chars.len() || t_chars[t_idx] != c {
+                if t_idx >= text.len() || !text[t_idx].matches(c, case) {
                     return false;
                 }
-                glob_match_recursive(pattern, text, p_idx + 1, t_idx + 1)
+                glob_match_recursive(pattern, text, p_idx + 1, t_idx + 1, case)
             }
         }
     }
@@ -0,0 +1,375 @@
+// Checks of workflow filter patterns, both against the files of the repository they live in and
+// as they are written in the workflow's YAML.
+
+use std::collections::BTreeSet;
+
+use crate::yaml::{self, ScalarStyle};
+use crate::{Filter, Glob, SourcePattern, Workflow};
+
+/// A `paths` or `paths-ignore` pattern that matches none of the repository's files.
+#[derive(Debug, Clone)]
+pub struct DeadPattern {
+    /// The event the filter belongs to, e.g. "push"
+    pub event: String,
+    /// "paths" or "paths-ignore"
+    pub key: String,
+    /// The position of the pattern in the filter
+    pub index: usize,
+    pub pattern: SourcePattern,
+    /// A pattern that matches files, found by correcting a likely typo in a directory or file name
+    pub suggestion: Option<String>,
+}
+
+/// Finds the path patterns of a workflow that match none of the given files.
+///
+/// The negation of a `!` pattern is ignored: a negation that excludes nothing is just as dead.
+/// When a literal part of the pattern is a few edits away from a name that exists at that level,
+/// the corrected pattern is offered as a suggestion, so `srcs/**` gets `src/**`. Files can be given
+/// as strings or bytes, like the ones [`read_git_index_bytes`](crate::read_git_index_bytes) lists.
+pub fn find_dead_patterns<P: AsRef<[u8]>>(workflow: &Workflow, files: &[P]) -> Vec<DeadPattern> {
+    let mut dead_patterns = Vec::new();
+
+    for event in &workflow.events {
+        for (key, patterns) in event.path_filters() {
+            for (index, pattern) in patterns.iter().enumerate() {
+                if files.iter().any(|file| pattern.glob.is_match_bytes(file.as_ref())) {
+                    continue;
+                }
+
+                dead_patterns.push(DeadPattern {
+                    event: event.name.clone(),
+                    key: key.to_string(),
+                    index,
+                    pattern: pattern.clone(),
+                    suggestion: suggest_pattern(pattern.glob.pattern(), files),
+                });
+            }
+        }
+    }
+
+    dead_patterns
+}
+
+/// A `paths` or `paths-ignore` pattern that matches files but decides none of them, because of where
+/// it is in the filter.
+#[derive(Debug, Clone)]
+pub struct ShadowedPattern {
+    pub event: String,
+    pub key: String,
+    pub index: usize,
+    pub pattern: SourcePattern,
+    /// Why the pattern has no effect
+    pub message: String,
+}
+
+/// Finds the path patterns of a workflow that have no effect on any of the given files where they
+/// are in their filter.
+///
+/// A pattern has no effect on a file when a later pattern also matches it and decides instead, or
+/// when it is a `!` negation and the patterns before it don't include the file. Patterns matching
+/// no file at all are left to [`find_dead_patterns`].
+pub fn find_shadowed_patterns<P: AsRef<[u8]>>(workflow: &Workflow, files: &[P]) -> Vec<ShadowedPattern> {
+    let mut shadowed_patterns = Vec::new();
+
+    for event in &workflow.events {
+        for (key, patterns) in event.path_filters() {
+            let matched: Vec<Vec<&[u8]>> = patterns
+                .iter()
+                .map(|pattern| {
+                    files.iter().map(|file| file.as_ref()).filter(|file| pattern.glob.is_match_bytes(file)).collect()
+                })
+                .collect();
+
+            for (index, pattern) in patterns.iter().enumerate() {
+                let files = &matched[index];
+                let earlier =
+                    Filter::from_globs(patterns[..index].iter().map(|pattern| pattern.glob.clone()).collect());
+                let excludes_nothing =
+                    pattern.glob.is_negation() && !files.iter().any(|file| earlier.is_match_bytes(file));
+                let no_effect = |file: &&[u8]| {
+                    patterns[index + 1..].iter().any(|later| later.glob.is_match_bytes(file))
+                        || pattern.glob.is_negation() && !earlier.is_match_bytes(file)
+                };
+                if files.is_empty() || !files.iter().all(no_effect) {
+                    continue;
+                }
+
+                // A single later pattern matching everything this one does is named
+                let covering = (index + 1..patterns.len())
+                    .rev()
+                    .find(|later| files.iter().all(|file| matched[*later].contains(file)));
+                let message = if excludes_nothing && patterns[..index].iter().all(|earlier| earlier.glob.is_negation())
+                {
+                    "excludes nothing, as no pattern before it includes files".to_string()
+                } else if excludes_nothing {
+                    "excludes no file that the patterns before it include".to_string()
+                } else if let Some(later) = covering {
+                    let effect = if patterns[later].glob.is_negation() { "excluded" } else { "included" };
+                    format!(
+                        "every file it matches is then {} by pattern {} `{}`",
+                        effect,
+                        later,
+                        patterns[later].glob.pattern()
+                    )
+                } else {
+                    "it has no effect on any of the files it matches".to_string()
+                };
+
+                shadowed_patterns.push(ShadowedPattern {
+                    event: event.name.clone(),
+                    key: key.to_string(),
+                    index,
+                    pattern: pattern.clone(),
+                    message,
+                });
+            }
+        }
+    }
+
+    shadowed_patterns
+}
+
+// Corrects the literal segments leading up to the first wildcard, one level at a time
+pub(crate) fn suggest_pattern<P: AsRef<[u8]>>(pattern: &str, files: &[P]) -> Option<String> {
+    let (body, prefix) = match pattern.strip_prefix('!') {
+        Some(rest) => (rest, "!"),
+        None => (pattern, ""),
+    };
+
+    let mut segments: Vec<String> = body.split('/').map(|segment| segment.to_string()).collect();
+    let mut corrected = false;
+
+    for idx in 0..segments.len() {
+        if segments[idx].contains(['*', '?', '+', '[']) {
+            break;
+        }
+
+        // Only the last segment can name a file
+        let is_last = idx == segments.len() - 1;
+        let names = names_below(&segments[..idx].join("/"), files, is_last);
+        if names.contains(segments[idx].as_str()) {
+            continue;
+        }
+
+        let closest = names
+            .iter()
+            .map(|name| (edit_distance(&segments[idx], name), *name))
+            .filter(|(distance, _)| *distance <= max_typo_distance(&segments[idx]))
+            .min_by_key(|(distance, _)| *distance);
+        let (_, name) = closest?;
+
+        segments[idx] = name.to_string();
+        corrected = true;
+    }
+
+    let suggestion = format!("{}{}", prefix, segments.join("/"));
+    let glob = Glob::new(&suggestion);
+    (corrected && files.iter().any(|file| glob.is_match_bytes(file.as_ref()))).then_some(suggestion)
+}
+
+// The names of the directories (and files, if asked) directly inside a directory. Names that are
+// not valid UTF-8 can't be written in a pattern, so they are left out.
+fn names_below<'a, P: AsRef<[u8]>>(dir: &str, files: &'a [P], include_files: bool) -> BTreeSet<&'a str> {
+    files
+        .iter()
+        .filter_map(|file| {
+            let file = file.as_ref();
+            if dir.is_empty() {
+                Some(file)
+            } else {
+                file.strip_prefix(dir.as_bytes())?.strip_prefix(b"/")
+            }
+        })
+        .filter_map(|rest| match rest.iter().position(|byte| *byte == b'/') {
+            Some(idx) => Some(&rest[..idx]),
+            None if include_files => Some(rest),
+            None => None,
+        })
+        .filter_map(|name| std::str::from_utf8(name).ok())
+        .collect()
+}
+
+// One edit per four characters, so that short names like "src" allow a single typo
+fn max_typo_distance(name: &str) -> usize {
+    (name.chars().count() / 4).max(1)
+}
+
+// Optimal string alignment distance: insertions, deletions, substitutions and adjacent
+// transpositions each count as one edit
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    distances[0] = (0..=b.len()).collect();
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut distance =
+                (distances[i - 1][j] + 1).min(distances[i][j - 1] + 1).min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
+
+// The filters of an event whose entries are patterns
+const FILTER_KEYS: &[&str] = &["branches", "branches-ignore", "tags", "tags-ignore", "paths", "paths-ignore"];
+
+/// An unquoted filter pattern that YAML reads as something other than a string, which makes GitHub
+/// reject the whole workflow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotingProblem {
+    pub event: String,
+    /// The filter, e.g. "paths" or "branches-ignore"
+    pub key: String,
+    /// The position of the pattern in the filter
+    pub index: usize,
+    /// 1-based position of the pattern in the file
+    pub line: usize,
+    pub column: usize,
+    /// The pattern as written
+    pub text: String,
+    pub message: String,
+    /// The pattern in single quotes, ready to replace `text`
+    pub quoted: String,
+}
+
+/// Finds the filter patterns of a workflow file that need quotes to be read as strings.
+///
+/// These are patterns starting with `*` (an alias), `!` (a tag), `[` or `{` (a flow collection), and
+/// patterns inside a flow sequence like `[a, b]` that contain brackets or braces, which end the
+/// pattern early. The workflow's YAML is inspected as written, since a pattern that YAML doesn't
+/// read as a string never makes it into [`parse_workflow`](crate::parse_workflow)'s result.
+pub fn find_quoting_problems(content: &str) -> Vec<QuotingProblem> {
+    let document = yaml::parse(content);
+    let lines: Vec<&str> = content.lines().collect();
+    let mut problems = Vec::new();
+
+    let Some(on) = document.get("on") else {
+        return problems;
+    };
+    for (event, settings) in on.entries() {
+        for key in FILTER_KEYS {
+            let Some(filter) = settings.get(key) else {
+                continue;
+            };
+
+            let in_flow = char_at(&lines, filter.line, filter.column) == Some('[');
+            let items = match &filter.value {
+                yaml::Value::Sequence(items) => items.as_slice(),
+                _ => std::slice::from_ref(filter),
+            };
+
+            for (index, item) in items.iter().enumerate() {
+                let Some(message) = quoting_problem(item, in_flow, &lines) else {
+                    continue;
+                };
+                let Some(text) = written_text(&lines, item.line, item.column, in_flow) else {
+                    continue;
+                };
+                problems.push(QuotingProblem {
+                    event: event.value.clone(),
+                    key: key.to_string(),
+                    index,
+                    line: item.line,
+                    column: item.column,
+                    quoted: format!("'{}'", text.replace('\'', "''")),
+                    text,
+                    message: message.to_string(),
+                });
+            }
+        }
+    }
+
+    problems
+}
+
+/// Replaces each pattern of [`find_quoting_problems`] with its quoted form.
+///
+/// Problems whose text is no longer where they point are skipped.
+pub fn apply_quoting(content: &str, problems: &[QuotingProblem]) -> String {
+    let mut lines: Vec<String> = content.split('\n').map(|line| line.to_string()).collect();
+
+    // Back to front, so that earlier positions on the same line stay valid
+    let mut problems: Vec<&QuotingProblem> = problems.iter().collect();
+    problems.sort_by_key(|problem| (problem.line, problem.column));
+
+    for problem in problems.iter().rev() {
+        let Some(line) = problem.line.checked_sub(1).and_then(|line| lines.get_mut(line)) else {
+            continue;
+        };
+        let Some((start, _)) = problem.column.checked_sub(1).and_then(|column| line.char_indices().nth(column)) else {
+            continue;
+        };
+        if line[start..].starts_with(&problem.text) {
+            line.replace_range(start..start + problem.text.len(), &problem.quoted);
+        }
+    }
+
+    lines.join("\n")
+}
+
+fn quoting_problem(item: &yaml::Node, in_flow: bool, lines: &[&str]) -> Option<&'static str> {
+    match &item.value {
+        yaml::Value::Scalar(scalar) if scalar.style == ScalarStyle::Plain => {
+            if scalar.value.starts_with('*') {
+                Some("unquoted, a pattern starting with `*` is read as a YAML alias")
+            } else if scalar.value.starts_with('!') {
+                Some("unquoted, a pattern starting with `!` is read as a YAML tag")
+            } else if in_flow
+                && (scalar.value.contains(['[', ']', '{', '}'])
+                    || written_text(lines, scalar.line, scalar.column, true).is_some_and(|text| text != scalar.raw))
+            {
+                Some("unquoted brackets or braces in a flow sequence end the pattern early")
+            } else {
+                None
+            }
+        }
+        yaml::Value::Sequence(_) => Some("unquoted, a pattern starting with `[` is read as a YAML flow sequence"),
+        yaml::Value::Mapping(_) => Some("unquoted, a pattern starting with `{` is read as a YAML flow mapping"),
+        _ => None,
+    }
+}
+
+fn char_at(lines: &[&str], line: usize, column: usize) -> Option<char> {
+    lines.get(line.checked_sub(1)?)?.chars().nth(column.checked_sub(1)?)
+}
+
+// The text of a pattern from its first character: up to the end of the line (or a comment) in a
+// block, or up to the next separator that isn't nested inside the pattern in a flow sequence
+fn written_text(lines: &[&str], line: usize, column: usize, in_flow: bool) -> Option<String> {
+    let rest: String = lines.get(line.checked_sub(1)?)?.chars().skip(column.checked_sub(1)?).collect();
+
+    if !in_flow {
+        let end = rest.find(" #").unwrap_or(rest.len());
+        return Some(rest[..end].trim_end().to_string());
+    }
+
+    let mut depth = 0;
+    let mut end = rest.len();
+    for (idx, c) in rest.char_indices() {
+        match c {
+            '[' | '{' => depth += 1,
+            ']' | '}' if depth == 0 => {
+                end = idx;
+                break;
+            }
+            ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                end = idx;
+                break;
+            }
+            _ => {}
+        }
+    }
+    Some(rest[..end].trim_end().to_string())
+}
@@ -0,0 +1,441 @@
+// A language server for the filters of GitHub Actions workflow files, speaking JSON-RPC over
+// standard input and output. It reports lint findings as diagnostics while the file is edited,
+// shows on hover which tracked files a path pattern matches, and offers the lint suggestions as
+// quick-fixes.
+// See https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::cli::{lint_workflow, tracked_files, Finding};
+use crate::json::Json;
+use crate::{parse_workflow, yaml};
+
+// How many of the matched files a hover lists
+const HOVER_FILES: usize = 10;
+
+// The largest message content read, far more than any workflow file needs
+const MAX_MESSAGE_LEN: usize = 16 << 20;
+
+/// Serves language server requests read from `input` until the client sends `exit` or closes the
+/// stream.
+pub fn run_lsp(input: &mut dyn BufRead, output: &mut dyn Write) -> io::Result<()> {
+    let mut server = Server { documents: HashMap::new(), files: HashMap::new(), output };
+
+    while let Some(content) = read_message(input)? {
+        // Without a parsed message there is no id to answer to, so the error has a null one
+        let message = match content.map(|content| Json::parse(&content)) {
+            Ok(Ok(message)) => message,
+            Ok(Err(error)) => {
+                server.respond(&Json::Null, Err((-32700, &format!("invalid JSON: {}", error))))?;
+                continue;
+            }
+            Err(error) => {
+                server.respond(&Json::Null, Err((-32600, error)))?;
+                continue;
+            }
+        };
+        let method = message.get("method").and_then(|method| method.as_str()).unwrap_or_default();
+        let params = message.get("params").unwrap_or(&Json::Null);
+
+        if method == "exit" {
+            break;
+        }
+        match message.get("id") {
+            Some(id) => {
+                let result = server.request(method, params);
+                server.respond(id, result)?;
+            }
+            None => server.notification(method, params)?,
+        }
+    }
+
+    Ok(())
+}
+
+// Reads the content of the next message, after its headers. Content over the size limit is skipped
+// and an error message returned in its place.
+fn read_message(input: &mut dyn BufRead) -> io::Result<Option<Result<String, &'static str>>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().ok();
+            }
+        }
+    }
+
+    let Some(length) = length else {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "message without a Content-Length header"));
+    };
+    if length > MAX_MESSAGE_LEN {
+        let skipped = io::copy(&mut (&mut *input).take(length as u64), &mut io::sink())?;
+        if skipped < length as u64 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "message content is truncated"));
+        }
+        return Ok(Some(Err("message too large")));
+    }
+    let mut content = vec![0; length];
+    input.read_exact(&mut content)?;
+    String::from_utf8(content)
+        .map(|content| Some(Ok(content)))
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+struct Server<'a> {
+    // The open workflow files, by URI
+    documents: HashMap<String, String>,
+    // The tracked files of each repository, or `None` when they can't be read
+    files: HashMap<PathBuf, Option<Vec<Vec<u8>>>>,
+    output: &'a mut dyn Write,
+}
+
+impl Server<'_> {
+    fn request(&mut self, method: &str, params: &Json) -> Result<Json, (i32, &'static str)> {
+        match method {
+            "initialize" => Ok(Json::object(vec![
+                (
+                    "capabilities",
+                    Json::object(vec![
+                        (
+                            "textDocumentSync",
+                            Json::object(vec![("openClose", true.into()), ("change", 1.into()), ("save", true.into())]),
+                        ),
+                        ("hoverProvider", true.into()),
+                        ("codeActionProvider", Json::object(vec![("codeActionKinds", vec!["quickfix"].into())])),
+                    ]),
+                ),
+                (
+                    "serverInfo",
+                    Json::object(vec![("name", "pathglob".into()), ("version", env!("CARGO_PKG_VERSION").into())]),
+                ),
+            ])),
+            "shutdown" => Ok(Json::Null),
+            "textDocument/hover" => Ok(self.hover(params)),
+            "textDocument/codeAction" => Ok(self.code_actions(params)),
+            _ => Err((-32601, "unknown method")),
+        }
+    }
+
+    fn notification(&mut self, method: &str, params: &Json) -> io::Result<()> {
+        let document = params.get("textDocument").unwrap_or(&Json::Null);
+        let Some(uri) = document.get("uri").and_then(|uri| uri.as_str()) else {
+            return Ok(());
+        };
+        let uri = uri.to_string();
+
+        match method {
+            "textDocument/didOpen" => {
+                let text = document.get("text").and_then(|text| text.as_str()).unwrap_or_default();
+                self.documents.insert(uri.clone(), text.to_string());
+                self.publish_diagnostics(&uri)
+            }
+            // The server asks for full content on every change
+            "textDocument/didChange" => {
+                let changes = params.get("contentChanges").map_or(&[][..], |changes| changes.as_array());
+                if let Some(text) = changes.last().and_then(|change| change.get("text")).and_then(|text| text.as_str())
+                {
+                    self.documents.insert(uri.clone(), text.to_string());
+                }
+                self.publish_diagnostics(&uri)
+            }
+            // A save may come along with a commit or a new file, so the tracked files are read again
+            "textDocument/didSave" => {
+                self.files.clear();
+                let uris: Vec<String> = self.documents.keys().cloned().collect();
+                uris.iter().try_for_each(|uri| self.publish_diagnostics(uri))
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                self.send(&notification("textDocument/publishDiagnostics", diagnostics_params(&uri, vec![])))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // The lint findings of an open workflow file, and its content
+    fn findings(&mut self, uri: &str) -> Option<(Vec<Finding>, &str)> {
+        let root = repository_root(&uri_to_path(uri)?)?;
+        let files = self.files.entry(root.clone()).or_insert_with(|| tracked_files(&root).ok());
+        let content = self.documents.get(uri)?;
+
+        let (mut findings, _) = match files {
+            Some(files) => lint_workflow(uri, content, files, false),
+            None => lint_workflow(uri, content, &[], false),
+        };
+        // Without the tracked files, every path pattern would look dead
+        if files.is_none() {
+            findings.retain(|finding| finding.rule != "dead-pattern");
+        }
+        Some((findings, content))
+    }
+
+    fn publish_diagnostics(&mut self, uri: &str) -> io::Result<()> {
+        let Some((findings, content)) = self.findings(uri) else {
+            return Ok(());
+        };
+        let lines: Vec<&str> = content.split('\n').collect();
+
+        let diagnostics = findings
+            .iter()
+            .map(|finding| {
+                let mut message = finding.message.clone();
+                if let Some(hint) = finding.hint() {
+                    message = format!("{}; {}", message, hint);
+                }
+                Json::object(vec![
+                    ("range", finding_range(&lines, finding)),
+                    ("severity", if finding.severity() == "error" { 1 } else { 2 }.into()),
+                    ("code", finding.rule.into()),
+                    ("source", "pathglob".into()),
+                    ("message", message.into()),
+                ])
+            })
+            .collect();
+        self.send(&notification("textDocument/publishDiagnostics", diagnostics_params(uri, diagnostics)))
+    }
+
+    // The files a path pattern under the cursor matches
+    fn hover(&mut self, params: &Json) -> Json {
+        let Some((uri, line, character)) = text_position(params) else {
+            return Json::Null;
+        };
+        let Some(root) = uri_to_path(&uri).and_then(|path| repository_root(&path)) else {
+            return Json::Null;
+        };
+        let Some(files) = self.files.entry(root.clone()).or_insert_with(|| tracked_files(&root).ok()) else {
+            return Json::Null;
+        };
+        let Some(content) = self.documents.get(&uri) else {
+            return Json::Null;
+        };
+
+        let lines: Vec<&str> = content.split('\n').collect();
+        let column = char_column(lines.get(line).copied().unwrap_or_default(), character) + 1;
+        let document = yaml::parse(content);
+        let scalars = document.descendant_scalars();
+
+        for event in parse_workflow(content).events {
+            for (_, patterns) in event.path_filters() {
+                for pattern in patterns {
+                    let Some(scalar) =
+                        scalars.iter().find(|scalar| (scalar.line, scalar.column) == (pattern.line, pattern.column))
+                    else {
+                        continue;
+                    };
+                    let end_column = pattern.column + scalar.raw.chars().count();
+                    if pattern.line != line + 1 || column < pattern.column || column >= end_column {
+                        continue;
+                    }
+
+                    let matched: Vec<&Vec<u8>> =
+                        files.iter().filter(|file| pattern.glob.is_match_bytes(file)).collect();
+                    let mut value = match matched.len() {
+                        0 => format!("`{}` matches no tracked file", pattern.glob.pattern()),
+                        1 => format!("`{}` matches 1 tracked file:\n", pattern.glob.pattern()),
+                        count => format!("`{}` matches {} tracked files:\n", pattern.glob.pattern(), count),
+                    };
+                    for file in matched.iter().take(HOVER_FILES) {
+                        value.push_str(&format!("\n- `{}`", String::from_utf8_lossy(file)));
+                    }
+                    if matched.len() > HOVER_FILES {
+                        value.push_str(&format!("\n- and {} more", matched.len() - HOVER_FILES));
+                    }
+
+                    return Json::object(vec![
+                        ("contents", Json::object(vec![("kind", "markdown".into()), ("value", value.into())])),
+                        ("range", range(&lines, pattern.line, pattern.column, end_column)),
+                    ]);
+                }
+            }
+        }
+
+        Json::Null
+    }
+
+    // A quick-fix for each finding with a suggestion on the lines of the requested range
+    fn code_actions(&mut self, params: &Json) -> Json {
+        let Some(uri) =
+            params.get("textDocument").and_then(|document| document.get("uri")).and_then(|uri| uri.as_str())
+        else {
+            return Json::Array(vec![]);
+        };
+        let line_of = |key: &str| {
+            params.get("range").and_then(|range| range.get(key)).and_then(|position| position.get("line"))?.as_usize()
+        };
+        let (Some(first_line), Some(last_line)) = (line_of("start"), line_of("end")) else {
+            return Json::Array(vec![]);
+        };
+        let Some((findings, content)) = self.findings(uri) else {
+            return Json::Array(vec![]);
+        };
+        let lines: Vec<&str> = content.split('\n').collect();
+
+        let actions: Vec<Json> = findings
+            .iter()
+            .filter(|finding| (first_line + 1..=last_line + 1).contains(&finding.line))
+            .filter_map(|finding| {
+                let fixed = finding.apply(content)?;
+                let title = if finding.rule == "yaml-quoting" {
+                    format!("Quote as {}", finding.suggestions[0])
+                } else {
+                    let suggestions: Vec<String> =
+                        finding.suggestions.iter().map(|suggestion| format!("`{}`", suggestion)).collect();
+                    format!("Replace with {}", suggestions.join(", "))
+                };
+                let edit = Json::Object(vec![(uri.to_string(), vec![text_edit(content, &fixed)].into())]);
+
+                Some(Json::object(vec![
+                    ("title", title.into()),
+                    ("kind", "quickfix".into()),
+                    ("isPreferred", true.into()),
+                    (
+                        "diagnostics",
+                        vec![Json::object(vec![
+                            ("range", finding_range(&lines, finding)),
+                            ("code", finding.rule.into()),
+                            ("source", "pathglob".into()),
+                        ])]
+                        .into(),
+                    ),
+                    ("edit", Json::object(vec![("changes", edit)])),
+                ]))
+            })
+            .collect();
+        actions.into()
+    }
+
+    fn respond(&mut self, id: &Json, result: Result<Json, (i32, &str)>) -> io::Result<()> {
+        let mut response = vec![("jsonrpc", "2.0".into()), ("id", id.clone())];
+        match result {
+            Ok(result) => response.push(("result", result)),
+            Err((code, message)) => response
+                .push(("error", Json::object(vec![("code", Json::Number(code as f64)), ("message", message.into())]))),
+        }
+        self.send(&Json::object(response))
+    }
+
+    fn send(&mut self, message: &Json) -> io::Result<()> {
+        let content = message.to_string();
+        write!(self.output, "Content-Length: {}\r\n\r\n{}", content.len(), content)?;
+        self.output.flush()
+    }
+}
+
+fn notification(method: &str, params: Json) -> Json {
+    Json::object(vec![("jsonrpc", "2.0".into()), ("method", method.into()), ("params", params)])
+}
+
+fn diagnostics_params(uri: &str, diagnostics: Vec<Json>) -> Json {
+    Json::object(vec![("uri", uri.into()), ("diagnostics", diagnostics.into())])
+}
+
+// The URI and 0-based position of a hover request
+fn text_position(params: &Json) -> Option<(String, usize, usize)> {
+    let uri = params.get("textDocument")?.get("uri")?.as_str()?;
+    let position = params.get("position")?;
+    Some((uri.to_string(), position.get("line")?.as_usize()?, position.get("character")?.as_usize()?))
+}
+
+// Only `file:` URIs name files the server can find the repository of
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?.as_bytes();
+    let mut decoded = Vec::new();
+    let mut idx = 0;
+    while idx < path.len() {
+        let escaped =
+            path.get(idx + 1..idx + 3).and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match (path[idx], escaped) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                idx += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                idx += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).ok().map(PathBuf::from)
+}
+
+// The repository of a file in its .github/workflows directory
+fn repository_root(path: &Path) -> Option<PathBuf> {
+    let extension = path.extension()?;
+    let workflows = path.parent()?;
+    if !(extension == "yml" || extension == "yaml") || !workflows.ends_with(".github/workflows") {
+        return None;
+    }
+    Some(workflows.parent()?.parent()?.to_path_buf())
+}
+
+fn finding_range(lines: &[&str], finding: &Finding) -> Json {
+    range(lines, finding.line, finding.column, finding.end_column)
+}
+
+// An LSP range on a single line, from 1-based character columns
+fn range(lines: &[&str], line: usize, column: usize, end_column: usize) -> Json {
+    let text = lines.get(line - 1).copied().unwrap_or_default();
+    Json::object(vec![
+        ("start", position(line - 1, utf16_column(text, column - 1))),
+        ("end", position(line - 1, utf16_column(text, end_column - 1))),
+    ])
+}
+
+fn position(line: usize, character: usize) -> Json {
+    Json::object(vec![("line", line.into()), ("character", character.into())])
+}
+
+// LSP positions count UTF-16 code units, the YAML reader counts characters
+fn utf16_column(text: &str, chars: usize) -> usize {
+    text.chars().take(chars).map(char::len_utf16).sum()
+}
+
+fn char_column(text: &str, utf16_units: usize) -> usize {
+    let mut units = 0;
+    text.chars()
+        .take_while(|c| {
+            units += c.len_utf16();
+            units <= utf16_units
+        })
+        .count()
+}
+
+// An edit replacing the lines that differ between the two contents
+fn text_edit(old: &str, new: &str) -> Json {
+    let old_lines: Vec<&str> = old.split('\n').collect();
+    let new_lines: Vec<&str> = new.split('\n').collect();
+
+    // Both sides keep at least one line, so the edit always has a line to start on
+    let max_common = old_lines.len().min(new_lines.len()) - 1;
+    let prefix = old_lines.iter().zip(&new_lines).take(max_common).take_while(|(a, b)| a == b).count();
+    let suffix = old_lines
+        .iter()
+        .rev()
+        .zip(new_lines.iter().rev())
+        .take(max_common - prefix)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let replaced = &new_lines[prefix..new_lines.len() - suffix];
+    let (end, new_text) = if suffix > 0 {
+        (position(old_lines.len() - suffix, 0), format!("{}\n", replaced.join("\n")))
+    } else {
+        let last = old_lines[old_lines.len() - 1];
+        (position(old_lines.len() - 1, utf16_column(last, last.chars().count())), replaced.join("\n"))
+    };
+
+    Json::object(vec![
+        ("range", Json::object(vec![("start", position(prefix, 0)), ("end", end)])),
+        ("newText", new_text.into()),
+    ])
+}
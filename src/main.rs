@@ -1,3 +1,6 @@
-fn main() {
-    println!("Run tests with 'cargo test'");
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    pathglob::run_cli(&args, &mut std::io::stdout())
 }
@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+
+use crate::json::Json;
+use crate::workflow::PATH_EVENTS;
+use crate::Workflow;
+
+/// Which workflows the files and directories of a repository trigger, see [`trigger_matrix`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TriggerMatrix {
+    /// The column names, in the order the workflows were given
+    pub workflows: Vec<String>,
+    /// Directories and files in tree order, each directory before its contents
+    pub rows: Vec<MatrixRow>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatrixRow {
+    /// The path of the file or directory, without a trailing `/`
+    pub path: String,
+    pub is_dir: bool,
+    /// The number of files the row covers: 1 for a file, everything below it for a directory
+    pub file_count: usize,
+    /// For each workflow, how many of those files trigger it
+    pub triggered: Vec<usize>,
+}
+
+/// Computes which workflows each file and directory triggers.
+///
+/// A file triggers a workflow when a change to it alone passes the path filters of one of its
+/// `push`, `pull_request` or `pull_request_target` events; branch filters are not considered.
+/// Directories aggregate all the files below them. With `max_depth`, only the entries that many
+/// segments deep or less get a row, so `Some(1)` gives the top-level directories and files. Files
+/// can be given as strings or bytes; the ones that are not valid UTF-8 are shown lossily.
+pub fn trigger_matrix<P: AsRef<[u8]>>(
+    workflows: &[(&str, &Workflow)],
+    files: &[P],
+    max_depth: Option<usize>,
+) -> TriggerMatrix {
+    let events: Vec<Vec<_>> = workflows
+        .iter()
+        .map(|(_, workflow)| {
+            workflow.events.iter().filter(|event| PATH_EVENTS.contains(&event.name.as_str())).collect()
+        })
+        .collect();
+
+    // Keyed by segments so that "docs/a.md" sorts before "docs-site"
+    let mut rows: BTreeMap<Vec<&[u8]>, MatrixRow> = BTreeMap::new();
+    for file in files {
+        let file = file.as_ref();
+        let triggered: Vec<bool> =
+            events.iter().map(|events| events.iter().any(|event| event.is_triggered_by(&[file]))).collect();
+
+        let segments: Vec<&[u8]> = file.split(|byte| *byte == b'/').collect();
+        let depth = max_depth.unwrap_or(segments.len()).min(segments.len());
+        for len in 1..=depth {
+            let row = rows.entry(segments[..len].to_vec()).or_insert_with(|| MatrixRow {
+                path: String::from_utf8_lossy(&segments[..len].join(&b'/')).into_owned(),
+                is_dir: len < segments.len(),
+                file_count: 0,
+                triggered: vec![0; workflows.len()],
+            });
+
+            row.file_count += 1;
+            for (count, triggered) in row.triggered.iter_mut().zip(&triggered) {
+                *count += *triggered as usize;
+            }
+        }
+    }
+
+    TriggerMatrix {
+        workflows: workflows.iter().map(|(name, _)| name.to_string()).collect(),
+        rows: rows.into_values().collect(),
+    }
+}
+
+impl TriggerMatrix {
+    /// One line per row: the path, "file" or "directory", the file count, then the number of files
+    /// triggering each workflow.
+    pub fn to_csv(&self) -> String {
+        let mut header = vec!["path".to_string(), "kind".to_string(), "files".to_string()];
+        header.extend(self.workflows.iter().cloned());
+
+        let mut csv = csv_line(&header);
+        for row in &self.rows {
+            let mut fields = vec![row.path.clone(), row.kind().to_string(), row.file_count.to_string()];
+            fields.extend(row.triggered.iter().map(|count| count.to_string()));
+            csv.push_str(&csv_line(&fields));
+        }
+        csv
+    }
+
+    /// A table with a check mark for the files that trigger a workflow and "all" or "n of m" for
+    /// directories.
+    pub fn to_markdown(&self) -> String {
+        let mut header = vec!["Path".to_string()];
+        header.extend(self.workflows.iter().map(|workflow| markdown_escape(workflow)));
+
+        let mut markdown = format!("| {} |\n|{}\n", header.join(" | "), "---|".repeat(header.len()));
+        for row in &self.rows {
+            let path = if row.is_dir { format!("{}/", row.path) } else { row.path.clone() };
+            let mut cells = vec![format!("`{}`", markdown_escape(&path))];
+            cells.extend(row.triggered.iter().map(|count| match *count {
+                0 => String::new(),
+                _ if !row.is_dir => "✓".to_string(),
+                count if count == row.file_count => "all".to_string(),
+                count => format!("{} of {}", count, row.file_count),
+            }));
+            markdown.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+        markdown
+    }
+
+    /// `{"workflows": [...], "rows": [{"path", "kind", "files", "triggered": [...]}]}`, where
+    /// `triggered` lines up with `workflows`.
+    pub fn to_json(&self) -> String {
+        self.json().to_string()
+    }
+
+    pub(crate) fn json(&self) -> Json {
+        let rows: Vec<Json> = self
+            .rows
+            .iter()
+            .map(|row| {
+                Json::object(vec![
+                    ("path", row.path.as_str().into()),
+                    ("kind", row.kind().into()),
+                    ("files", row.file_count.into()),
+                    ("triggered", row.triggered.clone().into()),
+                ])
+            })
+            .collect();
+
+        Json::object(vec![("workflows", self.workflows.clone().into()), ("rows", rows.into())])
+    }
+}
+
+impl MatrixRow {
+    fn kind(&self) -> &'static str {
+        if self.is_dir {
+            "directory"
+        } else {
+            "file"
+        }
+    }
+}
+
+fn csv_line(fields: &[String]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.clone()
+            }
+        })
+        .collect();
+    format!("{}\n", fields.join(","))
+}
+
+fn markdown_escape(text: &str) -> String {
+    text.replace('|', "\\|")
+}
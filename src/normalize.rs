@@ -0,0 +1,219 @@
+// Cleaning up candidate paths before they are matched. Patterns are written against paths like
+// `src/a.rs`, while paths from other tools come as `./src/a.rs`, `src\a.rs` or `src//a.rs`, and
+// from macOS with decomposed accents and kana.
+
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::unicode_tables::{COMBINING_CLASSES, DECOMPOSITIONS};
+
+/// What is done to a path before a [`Glob`](crate::Glob) matches it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Normalization {
+    /// Paths are matched as given.
+    #[default]
+    Off,
+    /// Backslashes become `/`, `.` segments and repeated or trailing separators are dropped, and
+    /// the path is put in Unicode NFC. Absolute paths, including Windows ones like `C:\\x`, and
+    /// paths with `..` segments are rejected.
+    Normalize,
+    /// Paths that [`Normalization::Normalize`] would change or reject are rejected.
+    Strict,
+}
+
+/// A path that normalisation rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathError {
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+impl std::error::Error for PathError {}
+
+impl Normalization {
+    /// The path as it is matched, borrowed when it needs no change.
+    pub fn apply(self, path: &str) -> Result<Cow<'_, str>, PathError> {
+        Ok(match self.apply_bytes(path.as_bytes())? {
+            Cow::Borrowed(_) => Cow::Borrowed(path),
+            Cow::Owned(bytes) => Cow::Owned(String::from_utf8(bytes).expect("normalisation keeps UTF-8 valid")),
+        })
+    }
+
+    /// Like [`Normalization::apply`], for a path that may not be valid UTF-8. Only its valid parts
+    /// are composed.
+    pub fn apply_bytes(self, path: &[u8]) -> Result<Cow<'_, [u8]>, PathError> {
+        if self == Normalization::Off {
+            return Ok(Cow::Borrowed(path));
+        }
+        let error = |message: &str| PathError {
+            path: String::from_utf8_lossy(path).into_owned(),
+            message: message.to_string(),
+        };
+
+        let slashed: Vec<u8> = path.iter().map(|byte| if *byte == b'\\' { b'/' } else { *byte }).collect();
+        let drive = slashed.len() >= 3 && slashed[0].is_ascii_alphabetic() && slashed[1..3] == *b":/";
+        if slashed.first() == Some(&b'/') || drive {
+            return Err(error("absolute paths can't be matched"));
+        }
+        let parts: Vec<&[u8]> = slashed.split(|byte| *byte == b'/').collect();
+        if parts.contains(&b"..".as_slice()) {
+            return Err(error("`..` segments can't be matched"));
+        }
+        let kept: Vec<&[u8]> = parts.iter().copied().filter(|part| !part.is_empty() && *part != b".").collect();
+        let normalized = compose(&kept.join(&b'/'));
+
+        if normalized == path {
+            return Ok(Cow::Borrowed(path));
+        }
+        if self == Normalization::Normalize {
+            return Ok(Cow::Owned(normalized));
+        }
+        // The first of the changes normalisation would make
+        let message = if path.contains(&b'\\') {
+            "backslash separator"
+        } else if parts.contains(&b".".as_slice()) {
+            "`.` segment"
+        } else if parts.last() == Some(&b"".as_slice()) {
+            "trailing separator"
+        } else if parts.contains(&b"".as_slice()) {
+            "repeated separator"
+        } else {
+            "not in Unicode NFC"
+        };
+        Err(error(message))
+    }
+}
+
+// Hangul syllables are decomposed into their jamo and composed arithmetically
+const HANGUL_BASE: u32 = 0xAC00;
+const LEAD_BASE: u32 = 0x1100;
+const VOWEL_BASE: u32 = 0x1161;
+const TRAIL_BASE: u32 = 0x11A7;
+const LEAD_COUNT: u32 = 19;
+const VOWEL_COUNT: u32 = 21;
+const TRAIL_COUNT: u32 = 28;
+const SYLLABLE_COUNT: u32 = LEAD_COUNT * VOWEL_COUNT * TRAIL_COUNT;
+
+fn compose(path: &[u8]) -> Vec<u8> {
+    let mut composed = Vec::with_capacity(path.len());
+    for chunk in path.utf8_chunks() {
+        composed.extend(nfc(chunk.valid()).bytes());
+        composed.extend(chunk.invalid());
+    }
+    composed
+}
+
+// The canonical decomposition of the text, with combining marks in canonical order, composed again
+fn nfc(text: &str) -> String {
+    if text.is_ascii() {
+        return text.to_string();
+    }
+
+    let mut chars = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        decompose(c, &mut chars);
+    }
+
+    // Each run of combining marks is sorted by class, keeping the order of marks of the same class
+    let mut start = 0;
+    while start < chars.len() {
+        let run = chars[start..].iter().take_while(|c| combining_class(**c) != 0).count();
+        chars[start..start + run].sort_by_key(|c| combining_class(*c));
+        start += run.max(1);
+    }
+
+    let mut composed: Vec<char> = Vec::with_capacity(chars.len());
+    let mut starter: Option<usize> = None;
+    // The class of the last character kept after the starter, 0 while there is none
+    let mut last_class = 0;
+    for c in chars {
+        let class = combining_class(c);
+        // A mark composes with the starter unless a character in between has the same or a higher class
+        if let Some(idx) = starter.filter(|_| last_class == 0 || last_class < class) {
+            if let Some(pair) = compose_pair(composed[idx], c) {
+                composed[idx] = pair;
+                continue;
+            }
+        }
+        if class == 0 {
+            starter = Some(composed.len());
+        }
+        last_class = class;
+        composed.push(c);
+    }
+    composed.into_iter().collect()
+}
+
+fn decompose(c: char, decomposed: &mut Vec<char>) {
+    let syllable_index = (c as u32).wrapping_sub(HANGUL_BASE);
+    if syllable_index < SYLLABLE_COUNT {
+        let jamo = [
+            LEAD_BASE + syllable_index / (VOWEL_COUNT * TRAIL_COUNT),
+            VOWEL_BASE + syllable_index % (VOWEL_COUNT * TRAIL_COUNT) / TRAIL_COUNT,
+            TRAIL_BASE + syllable_index % TRAIL_COUNT,
+        ];
+        let count = if syllable_index % TRAIL_COUNT == 0 { 2 } else { 3 };
+        decomposed.extend(jamo[..count].iter().filter_map(|code| char::from_u32(*code)));
+        return;
+    }
+
+    match DECOMPOSITIONS.binary_search_by_key(&c, |(composite, ..)| *composite) {
+        Ok(idx) => {
+            let (_, first, second, _) = DECOMPOSITIONS[idx];
+            decompose(first, decomposed);
+            if second != '\0' {
+                decompose(second, decomposed);
+            }
+        }
+        Err(_) => decomposed.push(c),
+    }
+}
+
+fn combining_class(c: char) -> u8 {
+    let range = COMBINING_CLASSES.binary_search_by(|(start, end, _)| {
+        if *end < c {
+            Ordering::Less
+        } else if *start > c {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    });
+    range.map_or(0, |idx| COMBINING_CLASSES[idx].2)
+}
+
+fn compose_pair(first: char, second: char) -> Option<char> {
+    let (first_code, second_code) = (first as u32, second as u32);
+    if (LEAD_BASE..LEAD_BASE + LEAD_COUNT).contains(&first_code)
+        && (VOWEL_BASE..VOWEL_BASE + VOWEL_COUNT).contains(&second_code)
+    {
+        let syllable = HANGUL_BASE + ((first_code - LEAD_BASE) * VOWEL_COUNT + second_code - VOWEL_BASE) * TRAIL_COUNT;
+        return char::from_u32(syllable);
+    }
+    let syllable_index = first_code.wrapping_sub(HANGUL_BASE);
+    if syllable_index < SYLLABLE_COUNT
+        && syllable_index % TRAIL_COUNT == 0
+        && (TRAIL_BASE + 1..TRAIL_BASE + TRAIL_COUNT).contains(&second_code)
+    {
+        return char::from_u32(first_code + second_code - TRAIL_BASE);
+    }
+
+    static COMPOSITIONS: OnceLock<HashMap<(char, char), char>> = OnceLock::new();
+    let compositions = COMPOSITIONS.get_or_init(|| {
+        DECOMPOSITIONS
+            .iter()
+            .filter(|(.., composes)| *composes)
+            .map(|(composite, first, second, _)| ((*first, *second), *composite))
+            .collect()
+    });
+    compositions.get(&(first, second)).copied()
+}
@@ -0,0 +1,355 @@
+// picomatch/micromatch syntax, as used by dorny/paths-filter and tj-actions/changed-files.
+// Both actions enable `dot: true`, so dotfiles are not treated specially during conversion.
+
+use std::collections::HashSet;
+
+use crate::wildcard::{
+    cross_product, expand_braces, expand_each, parse, split_segments, ClassItem, ExtKind, Node, NodeKind,
+    PICOMATCH_SYNTAX,
+};
+use crate::{expand_optionals, Pattern};
+
+/// The outcome of converting a pattern between GitHub and picomatch syntax.
+///
+/// `patterns` is a best-effort translation; every construct that could not be carried over with the
+/// same meaning is listed in `issues`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conversion {
+    pub patterns: Vec<String>,
+    pub issues: Vec<ConversionIssue>,
+}
+
+impl Conversion {
+    /// Whether the converted patterns match exactly the same paths as the source pattern.
+    pub fn is_exact(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    fn add_issue(&mut self, offset: usize, message: &str) {
+        let issue = ConversionIssue { offset, message: message.to_string() };
+        if !self.issues.contains(&issue) {
+            self.issues.push(issue);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConversionIssue {
+    /// Character offset of the offending construct in the source pattern
+    pub offset: usize,
+    pub message: String,
+}
+
+const NEGATION_ISSUE: &str =
+    "negation is ordered in GitHub filters but applies to the whole list in picomatch-based actions";
+
+/// Converts a GitHub filter pattern into a single picomatch pattern.
+///
+/// Optional characters (`x?`) are expanded like the GitHub matcher does and joined with braces.
+pub fn github_to_picomatch(pattern: &str) -> Conversion {
+    let mut conversion = Conversion { patterns: vec![], issues: vec![] };
+
+    let (body, prefix) = match pattern.strip_prefix('!') {
+        Some(rest) => {
+            conversion.add_issue(0, NEGATION_ISSUE);
+            (rest, "!")
+        }
+        None => (pattern, ""),
+    };
+    let body_offset = prefix.len();
+
+    if body.starts_with('?') {
+        // expand_optionals gives up on these and GitHub ends up comparing most segments literally
+        conversion.add_issue(body_offset, "a pattern starting with `?` is invalid in GitHub");
+        conversion.patterns.push(format!("{}{}", prefix, escape(body)));
+        return conversion;
+    }
+
+    add_github_segment_issues(body, body_offset, &mut conversion);
+
+    let variants: Vec<String> =
+        expand_optionals(body).iter().map(|variant| github_variant_to_picomatch(variant)).collect();
+
+    let converted = if variants.len() == 1 { variants[0].clone() } else { format!("{{{}}}", variants.join(",")) };
+    conversion.patterns.push(format!("{}{}", prefix, converted));
+    conversion
+}
+
+// Flags constructs that make the GitHub matcher reject every path
+fn add_github_segment_issues(body: &str, body_offset: usize, conversion: &mut Conversion) {
+    let chars: Vec<char> = body.chars().collect();
+    let mut segment_start = 0;
+
+    for (idx, c) in chars.iter().enumerate() {
+        if *c == '/' {
+            segment_start = idx + 1;
+            continue;
+        }
+
+        let in_double_star_suffix = chars[segment_start..].starts_with(&['*', '*']);
+        if in_double_star_suffix {
+            continue; // The suffix after ** is compared literally
+        }
+
+        match c {
+            '+' if idx == segment_start => {
+                conversion.add_issue(body_offset + idx, "`+` at the start of a segment never matches in GitHub")
+            }
+            '[' => {
+                let rest: String = chars[idx + 1..].iter().take_while(|c| **c != '/').collect();
+                match rest.find(']') {
+                    None => conversion.add_issue(body_offset + idx, "an unclosed `[` never matches in GitHub"),
+                    Some(0) => conversion.add_issue(body_offset + idx, "an empty `[]` never matches in GitHub"),
+                    Some(_) => {}
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn github_variant_to_picomatch(variant: &str) -> String {
+    let segments: Vec<String> = variant
+        .split('/')
+        .map(|part| {
+            if part == "**" {
+                "**".to_string()
+            } else if let Some(suffix) = part.strip_prefix("**") {
+                // Any number of directories, then a file name ending with the literal suffix
+                format!("**/*{}", escape(suffix))
+            } else if part.contains('*') || part.contains('+') || part.contains('[') {
+                github_segment_to_picomatch(part)
+            } else {
+                escape(part)
+            }
+        })
+        .collect();
+
+    segments.join("/")
+}
+
+// Mirrors glob_match_recursive, which is only reached once optionals have been expanded
+fn github_segment_to_picomatch(part: &str) -> String {
+    let chars: Vec<char> = part.chars().collect();
+    let mut converted = String::new();
+    let mut idx = 0;
+
+    while idx < chars.len() {
+        match chars[idx] {
+            '*' => {
+                converted.push('*');
+                idx += 1;
+            }
+            '+' => {
+                // Repeats the preceding pattern character literally; at the start it was flagged already
+                if idx > 0 {
+                    converted.push_str(&format!("+({})", escape_char(chars[idx - 1])));
+                }
+                idx += 1;
+            }
+            '[' => match chars[idx + 1..].iter().position(|c| *c == ']') {
+                Some(len) if len > 0 => {
+                    let content: String = chars[idx + 1..idx + 1 + len].iter().collect();
+                    converted.push('[');
+                    for (i, c) in content.chars().enumerate() {
+                        // A leading ! or ^ negates the class in picomatch but is literal in GitHub
+                        if i == 0 && (c == '!' || c == '^') || c == '\\' || c == '[' {
+                            converted.push('\\');
+                        }
+                        converted.push(c);
+                    }
+                    converted.push(']');
+                    idx += len + 2;
+                }
+                _ => {
+                    // Flagged already; keep the rest of the segment literal
+                    let rest: String = chars[idx..].iter().collect();
+                    converted.push_str(&escape(&rest));
+                    idx = chars.len();
+                }
+            },
+            c if idx + 1 < chars.len() && chars[idx + 1] == '+' => {
+                converted.push_str(&format!("+({})", escape_char(c)));
+                idx += 2;
+            }
+            c => {
+                converted.push_str(&escape_char(c));
+                idx += 1;
+            }
+        }
+    }
+
+    converted
+}
+
+fn escape(text: &str) -> String {
+    text.chars().map(escape_char).collect()
+}
+
+fn escape_char(c: char) -> String {
+    match c {
+        '*' | '?' | '[' | ']' | '{' | '}' | '(' | ')' | '!' | '@' | '+' | '\\' | '|' | ',' => format!("\\{}", c),
+        _ => c.to_string(),
+    }
+}
+
+/// Converts a picomatch pattern into one or more GitHub filter patterns.
+///
+/// Braces and `@(…)`/`?(…)` alternatives are expanded into separate patterns, so the result
+/// should be read as a list where any entry matching includes the path.
+pub fn picomatch_to_github(pattern: &str) -> Conversion {
+    let mut conversion = Conversion { patterns: vec![], issues: vec![] };
+
+    // "!(…)" at the start is an extglob rather than a negation
+    let (body, prefix) = match pattern.strip_prefix('!').filter(|rest| !rest.starts_with('(')) {
+        Some(rest) => {
+            conversion.add_issue(0, NEGATION_ISSUE);
+            (rest, "!")
+        }
+        None => (pattern, ""),
+    };
+
+    let nodes = parse(body, prefix.len(), PICOMATCH_SYNTAX);
+
+    let mut seen = HashSet::new();
+    let variants = expand_alternatives(&nodes).unwrap_or_else(|| vec![nodes.clone()]);
+    for variant in variants {
+        let converted = nodes_to_github(&variant, &mut conversion);
+        if converted.starts_with('?') || converted.starts_with('!') {
+            conversion.add_issue(prefix.len(), "GitHub cannot express a pattern starting with this character");
+        }
+
+        let converted = format!("{}{}", prefix, converted);
+        if seen.insert(converted.clone()) {
+            conversion.patterns.push(converted);
+        }
+    }
+
+    conversion
+}
+
+fn nodes_to_github(nodes: &[Node], conversion: &mut Conversion) -> String {
+    let mut converted = String::new();
+
+    for (idx, node) in nodes.iter().enumerate() {
+        match &node.kind {
+            NodeKind::Literal(c) => {
+                if matches!(c, '*' | '?' | '+' | '[' | ']') {
+                    conversion.add_issue(node.offset, "GitHub has no way to escape this character");
+                }
+                converted.push(*c);
+            }
+            NodeKind::Separator => converted.push('/'),
+            NodeKind::Star { double } => {
+                let segment_start = idx == 0 || nodes[idx - 1].kind == NodeKind::Separator;
+                let segment_end = idx + 1 == nodes.len() || nodes[idx + 1].kind == NodeKind::Separator;
+                if *double && segment_start && segment_end {
+                    converted.push_str("**");
+                } else if !converted.ends_with('*') {
+                    // Inside a segment ** is just *, while GitHub would read a leading ** as a suffix match
+                    converted.push('*');
+                }
+            }
+            NodeKind::Any => {
+                conversion.add_issue(node.offset, "GitHub has no single-character wildcard; widened to `*`");
+                if !converted.ends_with('*') {
+                    converted.push('*');
+                }
+            }
+            NodeKind::Class { negated, items } => {
+                if *negated {
+                    conversion.add_issue(node.offset, "GitHub does not support negated character classes");
+                }
+                converted.push('[');
+                for item in items {
+                    match item {
+                        ClassItem::Char(c) => converted.push(*c),
+                        ClassItem::Range(start, end) => converted.push_str(&format!("{}-{}", start, end)),
+                        ClassItem::Posix(name) => {
+                            conversion.add_issue(node.offset, "GitHub does not support POSIX character classes");
+                            converted.push_str(&format!("[:{}:]", name));
+                        }
+                    }
+                }
+                converted.push(']');
+            }
+            NodeKind::Ext(ExtKind::OneOrMore, alternatives) if single_char(alternatives).is_some() => {
+                converted.push(single_char(alternatives).unwrap());
+                converted.push('+');
+            }
+            NodeKind::Ext(..) => {
+                conversion.add_issue(node.offset, "GitHub has no equivalent for this extglob; widened to `*`");
+                if !converted.ends_with('*') {
+                    converted.push('*');
+                }
+            }
+            // Braces are only left when the pattern has too many alternatives to expand
+            NodeKind::Brace(_) => {
+                conversion.add_issue(node.offset, "too many alternatives to expand; widened to `*`");
+                if !converted.ends_with('*') {
+                    converted.push('*');
+                }
+            }
+        }
+    }
+
+    converted
+}
+
+fn single_char(alternatives: &[Vec<Node>]) -> Option<char> {
+    match alternatives {
+        [alternative] => match alternative.as_slice() {
+            [Node { kind: NodeKind::Literal(c), .. }] if c.is_alphanumeric() || matches!(c, '.' | '-' | '_') => {
+                Some(*c)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// Expands braces and the extglobs that are plain alternatives into separate node lists, or None when
+// there are too many of them
+fn expand_alternatives(nodes: &[Node]) -> Option<Vec<Vec<Node>>> {
+    let mut results: Vec<Vec<Node>> = vec![vec![]];
+
+    for node in nodes {
+        let choices = match &node.kind {
+            NodeKind::Brace(alternatives) | NodeKind::Ext(ExtKind::ExactlyOne, alternatives) => {
+                expand_each(alternatives, expand_alternatives)?
+            }
+            NodeKind::Ext(ExtKind::ZeroOrOne, alternatives) => {
+                std::iter::once(vec![]).chain(expand_each(alternatives, expand_alternatives)?).collect()
+            }
+            NodeKind::Ext(ExtKind::ZeroOrMore, alternatives) if single_char(alternatives).is_some() => {
+                let one_or_more =
+                    Node { kind: NodeKind::Ext(ExtKind::OneOrMore, alternatives.clone()), offset: node.offset };
+                vec![vec![], vec![one_or_more]]
+            }
+            _ => vec![vec![node.clone()]],
+        };
+        results = cross_product(results, &choices)?;
+    }
+
+    Some(results)
+}
+
+// Parses a pattern for Dialect::Picomatch, expanding braces into variants like picomatch does
+pub(crate) fn parse_pattern(pattern: &str) -> Vec<(Pattern, bool)> {
+    let (body, is_negation) = match pattern.strip_prefix('!').filter(|rest| !rest.starts_with('(')) {
+        Some(rest) => (rest, true),
+        None => (pattern, false),
+    };
+    let offset = pattern.len() - body.len();
+
+    // picomatch ignores a leading "./"
+    let (body, offset) = match body.strip_prefix("./") {
+        Some(rest) => (rest, offset + 2),
+        None => (body, offset),
+    };
+
+    expand_braces(&parse(body, offset, PICOMATCH_SYNTAX))
+        .iter()
+        .map(|variant| (Pattern { segments: split_segments(variant) }, is_negation))
+        .collect()
+}
@@ -0,0 +1,289 @@
+use crate::wildcard::{ClassItem, ExtKind, Node, NodeKind};
+use crate::{Case, Dialect, Glob, Segment};
+
+// A class that can never match, used where the matcher would reject every path.
+// Written without lookarounds so that RE2-style engines (Go, Rust) accept it too.
+const NEVER: &str = "[^\\s\\S]";
+
+impl Glob {
+    /// Translates the pattern into an anchored regular expression that matches the same paths.
+    ///
+    /// The leading `!` of a negation pattern is not part of the regex; check [`Glob::is_negation`].
+    /// The output only uses syntax shared by the common engines (Python `re`, Go `regexp`, PCRE,
+    /// JavaScript), except for `!(…)` extglobs, picomatch's dotfile rules and a `+` followed by
+    /// something that could match another repetition, which need lookaheads that RE2-style engines
+    /// lack. The empty path is outside the supported domain.
+    ///
+    /// A case-insensitive glob starts with the `(?i)` flag, which JavaScript spells as the `i` flag of
+    /// the `RegExp` instead. Whether it covers more than ASCII letters depends on the engine.
+    pub fn to_regex_string(&self) -> String {
+        let alternatives: Vec<String> =
+            self.variants.iter().map(|variant| segments_to_regex(&variant.segments, self.dialect)).collect();
+        let flags = if self.case == Case::Sensitive { "" } else { "(?i)" };
+
+        if alternatives.len() == 1 {
+            format!("{}^{}$", flags, alternatives[0])
+        } else {
+            format!("{}^(?:{})$", flags, alternatives.join("|"))
+        }
+    }
+}
+
+fn segments_to_regex(segments: &[Segment], dialect: Dialect) -> String {
+    let mut regex = String::new();
+
+    // Whether the next segment starts a fresh path segment, i.e. nothing was emitted yet or the
+    // previous `**` already consumed its trailing slash
+    let mut at_segment_start = true;
+
+    for (idx, segment) in segments.iter().enumerate() {
+        match segment {
+            Segment::DoubleStar if dialect.hides_dotfiles() => {
+                if !at_segment_start {
+                    regex.push_str("(?:/(?!\\.)[^/]*)*");
+                } else if idx == segments.len() - 1 {
+                    regex.push_str("(?:(?!\\.)[^/]*(?:/(?!\\.)[^/]*)*)?");
+                } else {
+                    regex.push_str("(?:(?!\\.)[^/]*/)*");
+                }
+            }
+
+            Segment::DoubleStar => {
+                if !at_segment_start {
+                    // "a/**" also matches "a" itself
+                    regex.push_str("(?:/.*)?");
+                } else if idx == segments.len() - 1 {
+                    regex.push_str(".*");
+                } else {
+                    // "**/b" also matches "b" at the root
+                    regex.push_str("(?:.*/)?");
+                }
+            }
+
+            Segment::DoubleStarWithSuffix(suffix) => {
+                if !at_segment_start {
+                    regex.push('/');
+                }
+                // The suffix is compared literally, across one or more segments
+                regex.push_str("(?:[^/]*/)*[^/]*");
+                regex.push_str(&escape(suffix));
+                at_segment_start = false;
+            }
+
+            Segment::Literal(literal) => {
+                if !at_segment_start {
+                    regex.push('/');
+                }
+                regex.push_str(&escape(literal));
+                at_segment_start = false;
+            }
+
+            Segment::Pattern(pattern) => {
+                if !at_segment_start {
+                    regex.push('/');
+                }
+                regex.push_str(&segment_pattern_to_regex(pattern));
+                at_segment_start = false;
+            }
+
+            Segment::Wildcard(nodes) => {
+                if !at_segment_start {
+                    regex.push('/');
+                }
+                let explicit_dot = matches!(nodes.first(), Some(Node { kind: NodeKind::Literal('.'), .. }));
+                if dialect.hides_dotfiles() && !explicit_dot {
+                    regex.push_str("(?!\\.)");
+                }
+                regex.push_str(&nodes_to_regex(nodes));
+                at_segment_start = false;
+            }
+        }
+    }
+
+    regex
+}
+
+// Mirrors glob_match_recursive character by character
+fn segment_pattern_to_regex(pattern: &str) -> String {
+    let p_chars: Vec<char> = pattern.chars().collect();
+    let mut regex = String::new();
+    let mut p_idx = 0;
+
+    while p_idx < p_chars.len() {
+        match p_chars[p_idx] {
+            '*' => {
+                regex.push_str("[^/]*");
+                p_idx += 1;
+            }
+
+            '+' => {
+                if p_idx == 0 {
+                    return NEVER.to_string();
+                }
+                // Repeats the preceding pattern character literally, even if it was special
+                regex.push_str(&escape_char(p_chars[p_idx - 1]));
+                regex.push_str(&possessive_plus(p_chars[p_idx - 1], &p_chars[p_idx + 1..]));
+                p_idx += 1;
+            }
+
+            '[' => {
+                let mut bracket_end = p_idx + 1;
+                while bracket_end < p_chars.len() && p_chars[bracket_end] != ']' {
+                    bracket_end += 1;
+                }
+
+                if bracket_end >= p_chars.len() {
+                    return NEVER.to_string(); // No closing bracket
+                }
+
+                let bracket_content: String = p_chars[(p_idx + 1)..bracket_end].iter().collect();
+                regex.push_str(&bracket_to_regex(&bracket_content));
+                p_idx = bracket_end + 1;
+            }
+
+            '?' => {
+                if p_idx == 0 {
+                    return NEVER.to_string();
+                }
+                regex.push_str(&format!("(?:{})?", escape_char(p_chars[p_idx - 1])));
+                p_idx += 1;
+            }
+
+            c => {
+                regex.push_str(&escape_char(c));
+                if p_idx + 1 < p_chars.len() && p_chars[p_idx + 1] == '+' {
+                    regex.push_str(&possessive_plus(c, &p_chars[p_idx + 2..]));
+                    p_idx += 2;
+                } else {
+                    p_idx += 1;
+                }
+            }
+        }
+    }
+
+    regex
+}
+
+// The `+` after a repeated character. The matcher takes every repetition without giving any back,
+// which a lookahead has to enforce when the rest of the segment could start with the character.
+fn possessive_plus(c: char, rest: &[char]) -> String {
+    let gives_back = match rest.first() {
+        None => false,
+        Some('*' | '+' | '?' | '[') => true,
+        Some(next) => c.to_lowercase().eq(next.to_lowercase()),
+    };
+
+    if gives_back {
+        format!("+(?!{})", escape_char(c))
+    } else {
+        "+".to_string()
+    }
+}
+
+// Mirrors matches_bracket_content
+fn bracket_to_regex(bracket_content: &str) -> String {
+    let chars: Vec<char> = bracket_content.chars().collect();
+    let mut class = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        if i + 2 < chars.len() && chars[i + 1] == '-' {
+            // Inverted ranges match nothing, so they are left out
+            if chars[i] <= chars[i + 2] {
+                class.push_str(&escape_class_char(chars[i]));
+                class.push('-');
+                class.push_str(&escape_class_char(chars[i + 2]));
+            }
+            i += 3;
+        } else {
+            class.push_str(&escape_class_char(chars[i]));
+            i += 1;
+        }
+    }
+
+    if class.is_empty() {
+        NEVER.to_string()
+    } else {
+        format!("[{}]", class)
+    }
+}
+
+fn nodes_to_regex(nodes: &[Node]) -> String {
+    nodes.iter().map(node_to_regex).collect()
+}
+
+fn node_to_regex(node: &Node) -> String {
+    match &node.kind {
+        NodeKind::Literal(c) => escape_char(*c),
+        NodeKind::Separator => "/".to_string(),
+        NodeKind::Any => "[^/]".to_string(),
+        NodeKind::Star { .. } => "[^/]*".to_string(),
+        NodeKind::Class { negated, items } => {
+            let class: String = items.iter().map(class_item_to_regex).collect();
+            match (negated, class.is_empty()) {
+                (false, true) => NEVER.to_string(),
+                (false, false) => format!("[{}]", class),
+                (true, _) => format!("[^/{}]", class),
+            }
+        }
+        NodeKind::Brace(alternatives) | NodeKind::Ext(ExtKind::ExactlyOne, alternatives) => {
+            format!("(?:{})", alternatives_to_regex(alternatives))
+        }
+        NodeKind::Ext(ExtKind::ZeroOrOne, alternatives) => format!("(?:{})?", alternatives_to_regex(alternatives)),
+        NodeKind::Ext(ExtKind::ZeroOrMore, alternatives) => format!("(?:{})*", alternatives_to_regex(alternatives)),
+        NodeKind::Ext(ExtKind::OneOrMore, alternatives) => format!("(?:{})+", alternatives_to_regex(alternatives)),
+        NodeKind::Ext(ExtKind::Not, alternatives) => {
+            format!("(?:(?!(?:{})$)[^/]*)", alternatives_to_regex(alternatives))
+        }
+    }
+}
+
+fn alternatives_to_regex(alternatives: &[Vec<Node>]) -> String {
+    alternatives.iter().map(|alternative| nodes_to_regex(alternative)).collect::<Vec<_>>().join("|")
+}
+
+fn class_item_to_regex(item: &ClassItem) -> String {
+    match item {
+        ClassItem::Char(c) => escape_class_char(*c),
+        ClassItem::Range(start, end) if start <= end => {
+            format!("{}-{}", escape_class_char(*start), escape_class_char(*end))
+        }
+        ClassItem::Range(..) => String::new(),
+        ClassItem::Posix(name) => match name.as_str() {
+            "alnum" => "a-zA-Z0-9",
+            "alpha" => "a-zA-Z",
+            "blank" => " \\t",
+            "cntrl" => "\\x00-\\x1f\\x7f",
+            "digit" => "0-9",
+            "graph" => "!-~",
+            "lower" => "a-z",
+            "print" => " -~",
+            "punct" => "!-/:-@\\[-`{-~",
+            // Vertical tab isn't ASCII whitespace to the matcher
+            "space" => " \\t\\n\\r\\f",
+            "upper" => "A-Z",
+            "word" => "a-zA-Z0-9_",
+            "xdigit" => "0-9a-fA-F",
+            _ => "",
+        }
+        .to_string(),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.chars().map(escape_char).collect()
+}
+
+fn escape_char(c: char) -> String {
+    match c {
+        '\\' | '.' | '+' | '*' | '?' | '(' | ')' | '|' | '[' | ']' | '{' | '}' | '^' | '$' => format!("\\{}", c),
+        _ => c.to_string(),
+    }
+}
+
+fn escape_class_char(c: char) -> String {
+    match c {
+        '\\' | ']' | '[' | '^' | '-' => format!("\\{}", c),
+        _ => c.to_string(),
+    }
+}
@@ -0,0 +1,212 @@
+// A playground for trying out patterns against the files of a repository: each line typed adds a
+// pattern to a list, and the files the list includes are shown along with the near misses, the
+// files a small change to the patterns would include.
+
+use std::io::{self, BufRead, Write};
+
+use crate::cli::{caret, decision, write_steps};
+use crate::lint::suggest_pattern;
+use crate::{Case, Dialect, Filter, Glob};
+
+// How many included files and near misses are listed after each change
+const LISTED_MATCHES: usize = 20;
+const LISTED_NEAR_MISSES: usize = 10;
+
+const HELP: &str = "\
+Type a pattern to add it to the list, or one of:
+  :explain <file or number>  show how each pattern treats a file
+  :pop                       remove the last pattern
+  :clear                     remove every pattern
+  :list                      show the patterns
+  :help                      show this help
+  :quit                      leave, like the end of input
+";
+
+/// Runs the playground on the given files, reading lines from `input` until `:quit` or the end of
+/// the input. An `interactive` session gets a prompt and colors.
+pub fn run_repl(
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+    files: &[String],
+    dialect: Dialect,
+    interactive: bool,
+) -> io::Result<()> {
+    let mut repl = Repl { files, dialect, interactive, patterns: vec![], listed: vec![] };
+    write!(out, "{} files. {}", files.len(), HELP)?;
+
+    loop {
+        if interactive {
+            write!(out, "> ")?;
+            out.flush()?;
+        }
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            break;
+        }
+        let line = line.trim();
+
+        match line.split_once(' ').map_or((line, ""), |(command, rest)| (command, rest.trim())) {
+            ("", _) => {}
+            (":quit", _) => break,
+            (":help", _) => write!(out, "{}", HELP)?,
+            (":list", _) => repl.write_patterns(out)?,
+            (":pop", _) => {
+                repl.patterns.pop();
+                repl.write_results(out)?;
+            }
+            (":clear", _) => {
+                repl.patterns.clear();
+                repl.write_results(out)?;
+            }
+            (":explain", target) => repl.explain(out, target)?,
+            (command, _) if command.starts_with(':') => writeln!(out, "unknown command {}, see :help", command)?,
+            _ => {
+                let glob = Glob::with_dialect(line, dialect);
+                for error in glob.errors() {
+                    writeln!(out, "error: {}", error.message)?;
+                    writeln!(out, "{}", caret(glob.pattern(), error.offset, error.len))?;
+                }
+                repl.patterns.push(glob);
+                repl.write_results(out)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+struct Repl<'a> {
+    files: &'a [String],
+    dialect: Dialect,
+    interactive: bool,
+    patterns: Vec<Glob>,
+    // The files numbered in the last results, for :explain
+    listed: Vec<String>,
+}
+
+impl Repl<'_> {
+    fn write_patterns(&self, out: &mut dyn Write) -> io::Result<()> {
+        if self.patterns.is_empty() {
+            return writeln!(out, "no patterns yet");
+        }
+        for (idx, glob) in self.patterns.iter().enumerate() {
+            writeln!(out, "  {} {}", idx, glob.pattern())?;
+        }
+        Ok(())
+    }
+
+    fn write_results(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        self.write_patterns(out)?;
+        let filter = Filter::from_globs(self.patterns.clone());
+        let (included, excluded): (Vec<&String>, Vec<&String>) =
+            self.files.iter().partition(|file| filter.is_match(file));
+        let relaxed = self.relaxed_patterns();
+        // A file that a negation excluded after a pattern included it, or one a relaxed pattern includes
+        let near_misses: Vec<(&String, String)> = excluded
+            .into_iter()
+            .filter_map(|file| {
+                let excluded_after_include = filter.deciding_glob(file).is_some_and(|deciding| {
+                    filter.globs()[..deciding].iter().any(|glob| !glob.is_negation() && glob.is_match(file))
+                });
+                if excluded_after_include {
+                    return Some((file, decision(&filter, file)));
+                }
+                relaxed.iter().find_map(|(reason, glob)| glob.is_match(file).then(|| (file, reason.clone())))
+            })
+            .collect();
+        self.listed.clear();
+
+        writeln!(out, "{} of {} files included", included.len(), self.files.len())?;
+        for file in included.iter().take(LISTED_MATCHES) {
+            self.listed.push(file.to_string());
+            writeln!(out, "  [{}] {}", self.listed.len(), self.paint(file, "32"))?;
+        }
+        if included.len() > LISTED_MATCHES {
+            writeln!(out, "  ... and {} more", included.len() - LISTED_MATCHES)?;
+        }
+
+        if near_misses.is_empty() {
+            return Ok(());
+        }
+        writeln!(out, "near misses:")?;
+        for (file, reason) in near_misses.iter().take(LISTED_NEAR_MISSES) {
+            self.listed.push(file.to_string());
+            writeln!(out, "  [{}] {}: {}", self.listed.len(), self.paint(file, "33"), reason)?;
+        }
+        if near_misses.len() > LISTED_NEAR_MISSES {
+            writeln!(out, "  ... and {} more", near_misses.len() - LISTED_NEAR_MISSES)?;
+        }
+        Ok(())
+    }
+
+    // The patterns relaxed in the ways people commonly get them wrong: with the case ignored, with
+    // `*` spanning directories, and with a typo corrected. Each comes with the reason to give for the
+    // files it includes.
+    fn relaxed_patterns(&self) -> Vec<(String, Glob)> {
+        let files: Vec<&str> = self.files.iter().map(|file| file.as_str()).collect();
+        let mut relaxed = Vec::new();
+
+        for (idx, glob) in self.patterns.iter().enumerate().filter(|(_, glob)| !glob.is_negation()) {
+            let pattern = glob.pattern();
+            relaxed.push((
+                format!("differs in case from pattern {} `{}`", idx, pattern),
+                glob.clone().with_case(Case::UnicodeInsensitive),
+            ));
+
+            let spanning = spanning_directories(pattern);
+            if spanning != pattern {
+                relaxed.push((
+                    format!("too deep for pattern {} `{}`, `{}` would include it", idx, pattern, spanning),
+                    Glob::with_dialect(&spanning, self.dialect),
+                ));
+            }
+
+            // Typos are only corrected for GitHub patterns
+            if let Some(suggestion) = suggest_pattern(pattern, &files).filter(|_| self.dialect == Dialect::GitHub) {
+                relaxed.push((
+                    format!("pattern {} `{}` would include it as `{}`", idx, pattern, suggestion),
+                    Glob::new(&suggestion),
+                ));
+            }
+        }
+        relaxed
+    }
+
+    fn explain(&self, out: &mut dyn Write, target: &str) -> io::Result<()> {
+        let path = match target.parse::<usize>() {
+            Ok(number) => match number.checked_sub(1).and_then(|idx| self.listed.get(idx)) {
+                Some(path) => path.as_str(),
+                None => return writeln!(out, "no file numbered {}", number),
+            },
+            Err(_) if target.is_empty() => return writeln!(out, "usage: :explain <file or number>"),
+            Err(_) => target,
+        };
+
+        let filter = Filter::from_globs(self.patterns.clone());
+        writeln!(out, "{}: {}", path, decision(&filter, path))?;
+        write_steps(out, &filter, path)
+    }
+
+    fn paint(&self, text: &str, color: &str) -> String {
+        if self.interactive {
+            format!("\x1b[{}m{}\x1b[0m", color, text)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+// The pattern with each single `*` doubled, so that it matches in subdirectories as well
+fn spanning_directories(pattern: &str) -> String {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut spanning = String::new();
+
+    for (idx, c) in chars.iter().enumerate() {
+        spanning.push(*c);
+        let lone_star = *c == '*' && idx.checked_sub(1).is_none_or(|previous| chars[previous] != '*');
+        if lone_star && chars.get(idx + 1) != Some(&'*') {
+            spanning.push('*');
+        }
+    }
+    spanning
+}
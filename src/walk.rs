@@ -0,0 +1,48 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::Filter;
+
+/// Lists the files below `root` that the filter matches, as sorted `/`-separated paths relative to
+/// `root`.
+///
+/// Directories that no pattern could match anything in are skipped without being read, so
+/// `docs/**` never enters `src/`. The `.git` directory is always skipped and symbolic links are
+/// listed like files rather than followed, the same way git tracks them. Names that are not valid
+/// UTF-8 are converted lossily, see [`find_files_bytes`] for the paths as they are.
+pub fn find_files(root: &Path, filter: &Filter) -> io::Result<Vec<String>> {
+    Ok(find_files_bytes(root, filter)?.iter().map(|path| String::from_utf8_lossy(path).into_owned()).collect())
+}
+
+/// Like [`find_files`], with the paths as the encoded bytes of the file names, see
+/// [`Glob::is_match_os`](crate::Glob::is_match_os). Names are matched as they are.
+pub fn find_files_bytes(root: &Path, filter: &Filter) -> io::Result<Vec<Vec<u8>>> {
+    let mut files = Vec::new();
+    walk_dir(root, b"", filter, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+fn walk_dir(dir: &Path, prefix: &[u8], filter: &Filter, files: &mut Vec<Vec<u8>>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let mut path = prefix.to_vec();
+        if !prefix.is_empty() {
+            path.push(b'/');
+        }
+        path.extend_from_slice(name.as_encoded_bytes());
+
+        if entry.file_type()?.is_dir() {
+            if name == ".git" || !filter.could_match_under_bytes(&path) {
+                continue;
+            }
+            walk_dir(&entry.path(), &path, filter, files)?;
+        } else if filter.is_match_bytes(&path) {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
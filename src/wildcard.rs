@@ -0,0 +1,583 @@
+// Wildcard syntax shared by the dialects other than GitHub's: `*`, `?`, classes with negation,
+// backslash escapes, braces and extglobs. Patterns are parsed into nodes once and matched one
+// path segment at a time.
+
+use crate::{Case, PathChar, Segment};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Node {
+    pub(crate) kind: NodeKind,
+    // Character offset in the source pattern
+    pub(crate) offset: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum NodeKind {
+    Literal(char),                                  // "a", "\*"
+    Separator,                                      // "/"
+    Star { double: bool },                          // "*", "**"
+    Any,                                            // "?"
+    Class { negated: bool, items: Vec<ClassItem> }, // "[a-z]", "[!._]"
+    Brace(Vec<Vec<Node>>),                          // "{a,b}", "{1..3}"
+    Ext(ExtKind, Vec<Vec<Node>>),                   // "@(a|b)", "!(*.md)"
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ClassItem {
+    Char(char),
+    Range(char, char),
+    Posix(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ExtKind {
+    ExactlyOne, // @(…)
+    ZeroOrOne,  // ?(…)
+    ZeroOrMore, // *(…)
+    OneOrMore,  // +(…)
+    Not,        // !(…)
+}
+
+/// The optional constructs a dialect understands; `*`, `**` and `?` are always available.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Syntax {
+    pub(crate) classes: bool,
+    pub(crate) escapes: bool,
+    pub(crate) braces: bool,
+    // Numeric and alphabetic sequences like "{1..3}" inside braces
+    pub(crate) brace_ranges: bool,
+    pub(crate) extglobs: bool,
+}
+
+pub(crate) const PICOMATCH_SYNTAX: Syntax =
+    Syntax { classes: true, escapes: true, braces: true, brace_ranges: true, extglobs: true };
+
+pub(crate) fn parse(pattern: &str, base_offset: usize, syntax: Syntax) -> Vec<Node> {
+    let chars: Vec<char> = pattern.chars().collect();
+    parse_chars(&chars, base_offset, syntax, 0)
+}
+
+// How deeply braces and extglobs can nest. Deeper groups are read literally, so that the recursive
+// parser and matcher can't be made to overflow the stack.
+const MAX_NESTING: usize = 64;
+
+// `depth` is the number of groups around `chars`
+fn parse_chars(chars: &[char], base_offset: usize, syntax: Syntax, depth: usize) -> Vec<Node> {
+    let nested = depth < MAX_NESTING;
+    let mut nodes = Vec::new();
+    let mut idx = 0;
+
+    while idx < chars.len() {
+        let offset = base_offset + idx;
+        let c = chars[idx];
+
+        // Extglobs: a marker immediately followed by a balanced group
+        if syntax.extglobs && nested && matches!(c, '@' | '?' | '*' | '+' | '!') && chars.get(idx + 1) == Some(&'(') {
+            if let Some(close) = find_closing(chars, idx + 1, '(', ')') {
+                let kind = match c {
+                    '@' => ExtKind::ExactlyOne,
+                    '?' => ExtKind::ZeroOrOne,
+                    '*' => ExtKind::ZeroOrMore,
+                    '+' => ExtKind::OneOrMore,
+                    _ => ExtKind::Not,
+                };
+                let alternatives = split_top_level(chars, idx + 2, close, '|')
+                    .into_iter()
+                    .map(|(start, end)| parse_chars(&chars[start..end], base_offset + start, syntax, depth + 1))
+                    .collect();
+                nodes.push(Node { kind: NodeKind::Ext(kind, alternatives), offset });
+                idx = close + 1;
+                continue;
+            }
+        }
+
+        match c {
+            '\\' if syntax.escapes && idx + 1 < chars.len() => {
+                nodes.push(Node { kind: NodeKind::Literal(chars[idx + 1]), offset });
+                idx += 2;
+            }
+            '/' => {
+                nodes.push(Node { kind: NodeKind::Separator, offset });
+                idx += 1;
+            }
+            '*' => {
+                let mut end = idx;
+                while end < chars.len() && chars[end] == '*' {
+                    end += 1;
+                }
+                nodes.push(Node { kind: NodeKind::Star { double: end - idx > 1 }, offset });
+                idx = end;
+            }
+            '?' => {
+                nodes.push(Node { kind: NodeKind::Any, offset });
+                idx += 1;
+            }
+            '[' if syntax.classes => match parse_class(chars, idx) {
+                Some((kind, end)) => {
+                    nodes.push(Node { kind, offset });
+                    idx = end;
+                }
+                None => {
+                    nodes.push(Node { kind: NodeKind::Literal('['), offset });
+                    idx += 1;
+                }
+            },
+            '{' if syntax.braces && nested => match parse_brace(chars, idx, base_offset, syntax, depth) {
+                Some((kind, end)) => {
+                    nodes.push(Node { kind, offset });
+                    idx = end;
+                }
+                None => {
+                    nodes.push(Node { kind: NodeKind::Literal('{'), offset });
+                    idx += 1;
+                }
+            },
+            c => {
+                nodes.push(Node { kind: NodeKind::Literal(c), offset });
+                idx += 1;
+            }
+        }
+    }
+
+    nodes
+}
+
+// Returns the index of the bracket closing the one at `open`, skipping escapes and nested pairs
+fn find_closing(chars: &[char], open: usize, open_char: char, close_char: char) -> Option<usize> {
+    let mut depth = 0;
+    let mut idx = open;
+
+    while idx < chars.len() {
+        match chars[idx] {
+            '\\' => idx += 1,
+            c if c == open_char => depth += 1,
+            c if c == close_char => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(idx);
+                }
+            }
+            _ => {}
+        }
+        idx += 1;
+    }
+
+    None
+}
+
+// Splits chars[start..end] at separators outside of nested groups, returning index ranges
+fn split_top_level(chars: &[char], start: usize, end: usize, separator: char) -> Vec<(usize, usize)> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut part_start = start;
+    let mut idx = start;
+
+    while idx < end {
+        match chars[idx] {
+            '\\' => idx += 1,
+            '(' | '{' | '[' => depth += 1,
+            ')' | '}' | ']' => depth -= 1,
+            c if c == separator && depth == 0 => {
+                parts.push((part_start, idx));
+                part_start = idx + 1;
+            }
+            _ => {}
+        }
+        idx += 1;
+    }
+
+    parts.push((part_start, end));
+    parts
+}
+
+fn parse_class(chars: &[char], open: usize) -> Option<(NodeKind, usize)> {
+    let mut idx = open + 1;
+    let negated = matches!(chars.get(idx), Some('!') | Some('^'));
+    if negated {
+        idx += 1;
+    }
+
+    let mut items = Vec::new();
+    let mut first = true;
+
+    while idx < chars.len() {
+        let c = chars[idx];
+
+        // A ] right after the opening bracket is a member, not the end of the class
+        if c == ']' && !first {
+            return Some((NodeKind::Class { negated, items }, idx + 1));
+        }
+        first = false;
+
+        if c == '[' && chars.get(idx + 1) == Some(&':') {
+            let name_end = (idx + 2..chars.len().saturating_sub(1)).find(|i| chars[*i] == ':' && chars[*i + 1] == ']');
+            if let Some(name_end) = name_end {
+                items.push(ClassItem::Posix(chars[idx + 2..name_end].iter().collect()));
+                idx = name_end + 2;
+                continue;
+            }
+        }
+
+        let (start, next) = if c == '\\' && idx + 1 < chars.len() { (chars[idx + 1], idx + 2) } else { (c, idx + 1) };
+
+        if chars.get(next) == Some(&'-') && next + 1 < chars.len() && chars[next + 1] != ']' {
+            let (end, after) = if chars[next + 1] == '\\' && next + 2 < chars.len() {
+                (chars[next + 2], next + 3)
+            } else {
+                (chars[next + 1], next + 2)
+            };
+            items.push(ClassItem::Range(start, end));
+            idx = after;
+        } else {
+            items.push(ClassItem::Char(start));
+            idx = next;
+        }
+    }
+
+    None
+}
+
+fn parse_brace(
+    chars: &[char],
+    open: usize,
+    base_offset: usize,
+    syntax: Syntax,
+    depth: usize,
+) -> Option<(NodeKind, usize)> {
+    let close = find_closing(chars, open, '{', '}')?;
+    let parts = split_top_level(chars, open + 1, close, ',');
+
+    if parts.len() > 1 {
+        let alternatives = parts
+            .into_iter()
+            .map(|(start, end)| parse_chars(&chars[start..end], base_offset + start, syntax, depth + 1))
+            .collect();
+        return Some((NodeKind::Brace(alternatives), close + 1));
+    }
+
+    if !syntax.brace_ranges {
+        return None;
+    }
+
+    let content: String = chars[open + 1..close].iter().collect();
+    let alternatives = expand_range(&content)?
+        .into_iter()
+        .map(|value| value.chars().map(|c| Node { kind: NodeKind::Literal(c), offset: base_offset + open }).collect())
+        .collect();
+    Some((NodeKind::Brace(alternatives), close + 1))
+}
+
+// The most values a range like "{1..3}" expands to, picomatch's default rangeLimit. Larger ranges
+// are read literally.
+const RANGE_LIMIT: i128 = 1000;
+
+// The most variants braces expand a pattern into. A pattern with more is matched without expanding
+// its braces, so that only their alternatives without a slash can match.
+const EXPANSION_LIMIT: usize = 10_000;
+
+// "1..3", "a..e", "01..10" and "1..10..2", as supported by picomatch's brace expansion
+fn expand_range(content: &str) -> Option<Vec<String>> {
+    let parts: Vec<&str> = content.split("..").collect();
+    if parts.len() != 2 && parts.len() != 3 {
+        return None;
+    }
+
+    let step: usize = match parts.get(2) {
+        Some(step) => step.trim_start_matches('-').parse().ok().filter(|step| *step > 0)?,
+        None => 1,
+    };
+
+    if let (Ok(start), Ok(end)) = (parts[0].parse::<i64>(), parts[1].parse::<i64>()) {
+        if (i128::from(start) - i128::from(end)).abs() / step as i128 >= RANGE_LIMIT {
+            return None;
+        }
+        let padded = [parts[0], parts[1]]
+            .iter()
+            .any(|part| part.trim_start_matches('-').len() > 1 && part.trim_start_matches('-').starts_with('0'));
+        let width = if padded { parts[0].len().max(parts[1].len()) } else { 0 };
+
+        let values: Vec<i64> = if start <= end {
+            (start..=end).step_by(step).collect()
+        } else {
+            (end..=start).rev().step_by(step).collect()
+        };
+        return Some(values.into_iter().map(|value| format!("{:0width$}", value, width = width)).collect());
+    }
+
+    // Letters are ASCII, so they always stay within the limit
+    let (start, end) = (single_char_of(parts[0])?, single_char_of(parts[1])?);
+    let values: Vec<char> =
+        if start <= end { (start..=end).step_by(step).collect() } else { (end..=start).rev().step_by(step).collect() };
+    Some(values.into_iter().map(|value| value.to_string()).collect())
+}
+
+fn single_char_of(text: &str) -> Option<char> {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_alphabetic() => Some(c),
+        _ => None,
+    }
+}
+
+/// Whether the nodes match the whole text, which is a single path segment.
+pub(crate) fn match_nodes(nodes: &[Node], text: &[PathChar], case: Case) -> bool {
+    match_ends(nodes, text, 0, case).contains(&text.len())
+}
+
+// Every position in text where a match of all nodes starting at `start` can end
+fn match_ends(nodes: &[Node], text: &[PathChar], start: usize, case: Case) -> Vec<usize> {
+    let mut positions = vec![start];
+
+    for node in nodes {
+        let mut next_positions = Vec::new();
+        for position in &positions {
+            for end in node_ends(node, text, *position, case) {
+                if !next_positions.contains(&end) {
+                    next_positions.push(end);
+                }
+            }
+        }
+
+        if next_positions.is_empty() {
+            return next_positions;
+        }
+        positions = next_positions;
+    }
+
+    positions
+}
+
+fn node_ends(node: &Node, text: &[PathChar], start: usize, case: Case) -> Vec<usize> {
+    match &node.kind {
+        NodeKind::Literal(c) => single_char_end(text, start, |t| t.matches(*c, case)),
+        NodeKind::Separator => single_char_end(text, start, |t| t == PathChar::Char('/')),
+        NodeKind::Any => single_char_end(text, start, |_| true),
+        NodeKind::Class { negated, items } => single_char_end(text, start, |t| {
+            t.in_class(case, |t| items.iter().any(|item| item.contains(t))) != *negated
+        }),
+
+        // A segment never contains a slash, so ** behaves like * here
+        NodeKind::Star { .. } => (start..=text.len()).collect(),
+
+        NodeKind::Brace(alternatives) | NodeKind::Ext(ExtKind::ExactlyOne, alternatives) => {
+            alternatives_ends(alternatives, text, start, case)
+        }
+
+        NodeKind::Ext(ExtKind::ZeroOrOne, alternatives) => {
+            let mut ends = alternatives_ends(alternatives, text, start, case);
+            if !ends.contains(&start) {
+                ends.push(start);
+            }
+            ends
+        }
+
+        NodeKind::Ext(ExtKind::OneOrMore, alternatives) => repeated_ends(alternatives, text, start, case),
+
+        NodeKind::Ext(ExtKind::ZeroOrMore, alternatives) => {
+            let mut ends = repeated_ends(alternatives, text, start, case);
+            if !ends.contains(&start) {
+                ends.push(start);
+            }
+            ends
+        }
+
+        NodeKind::Ext(ExtKind::Not, alternatives) => (start..=text.len())
+            .filter(|end| {
+                let candidate = &text[start..*end];
+                !alternatives.iter().any(|alternative| match_nodes(alternative, candidate, case))
+            })
+            .collect(),
+    }
+}
+
+fn single_char_end(text: &[PathChar], start: usize, accept: impl Fn(PathChar) -> bool) -> Vec<usize> {
+    if start < text.len() && accept(text[start]) {
+        vec![start + 1]
+    } else {
+        vec![]
+    }
+}
+
+fn alternatives_ends(alternatives: &[Vec<Node>], text: &[PathChar], start: usize, case: Case) -> Vec<usize> {
+    let mut ends = Vec::new();
+    for alternative in alternatives {
+        for end in match_ends(alternative, text, start, case) {
+            if !ends.contains(&end) {
+                ends.push(end);
+            }
+        }
+    }
+    ends
+}
+
+// One or more repetitions; repetitions that consume nothing are ignored so this terminates
+fn repeated_ends(alternatives: &[Vec<Node>], text: &[PathChar], start: usize, case: Case) -> Vec<usize> {
+    let mut ends: Vec<usize> = Vec::new();
+    let mut frontier = vec![start];
+
+    while let Some(position) = frontier.pop() {
+        for end in alternatives_ends(alternatives, text, position, case) {
+            if end > position && !ends.contains(&end) {
+                ends.push(end);
+                frontier.push(end);
+            }
+        }
+    }
+
+    ends
+}
+
+impl ClassItem {
+    fn contains(&self, c: char) -> bool {
+        match self {
+            ClassItem::Char(member) => *member == c,
+            ClassItem::Range(start, end) => *start <= c && c <= *end,
+            ClassItem::Posix(name) => match name.as_str() {
+                "alnum" => c.is_ascii_alphanumeric(),
+                "alpha" => c.is_ascii_alphabetic(),
+                "blank" => c == ' ' || c == '\t',
+                "cntrl" => c.is_ascii_control(),
+                "digit" => c.is_ascii_digit(),
+                "graph" => c.is_ascii_graphic(),
+                "lower" => c.is_ascii_lowercase(),
+                "print" => c.is_ascii_graphic() || c == ' ',
+                "punct" => c.is_ascii_punctuation(),
+                "space" => c.is_ascii_whitespace(),
+                "upper" => c.is_ascii_uppercase(),
+                "word" => c.is_ascii_alphanumeric() || c == '_',
+                "xdigit" => c.is_ascii_hexdigit(),
+                _ => false,
+            },
+        }
+    }
+}
+
+// Expands braces into separate node lists, since their alternatives may contain slashes. A pattern
+// with too many variants is kept as it is.
+pub(crate) fn expand_braces(nodes: &[Node]) -> Vec<Vec<Node>> {
+    try_expand_braces(nodes).unwrap_or_else(|| vec![nodes.to_vec()])
+}
+
+pub(crate) fn try_expand_braces(nodes: &[Node]) -> Option<Vec<Vec<Node>>> {
+    let mut results: Vec<Vec<Node>> = vec![vec![]];
+
+    for node in nodes {
+        let choices = match &node.kind {
+            NodeKind::Brace(alternatives) => expand_each(alternatives, try_expand_braces)?,
+            _ => vec![vec![node.clone()]],
+        };
+        results = cross_product(results, &choices)?;
+    }
+
+    Some(results)
+}
+
+// The expansions of all alternatives, or None once there are more than EXPANSION_LIMIT of them
+pub(crate) fn expand_each(
+    alternatives: &[Vec<Node>],
+    expand: fn(&[Node]) -> Option<Vec<Vec<Node>>>,
+) -> Option<Vec<Vec<Node>>> {
+    let mut expanded = Vec::new();
+    for alternative in alternatives {
+        expanded.extend(expand(alternative)?);
+        if expanded.len() > EXPANSION_LIMIT {
+            return None;
+        }
+    }
+    Some(expanded)
+}
+
+// Each variant followed by each choice, or None when that makes more than EXPANSION_LIMIT variants
+pub(crate) fn cross_product(mut variants: Vec<Vec<Node>>, choices: &[Vec<Node>]) -> Option<Vec<Vec<Node>>> {
+    if variants.len() * choices.len() > EXPANSION_LIMIT {
+        return None;
+    }
+
+    // Most nodes aren't alternatives, so extend the variants instead of copying them for every node
+    if let [choice] = choices {
+        for variant in &mut variants {
+            variant.extend(choice.iter().cloned());
+        }
+        return Some(variants);
+    }
+
+    Some(
+        variants
+            .iter()
+            .flat_map(|prefix| {
+                choices.iter().map(move |choice| {
+                    let mut combined = prefix.clone();
+                    combined.extend(choice.iter().cloned());
+                    combined
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Groups nodes into path segments, keeping plain literals and `**` in the shared segment forms.
+pub(crate) fn split_segments(nodes: &[Node]) -> Vec<Segment> {
+    nodes
+        .split(|node| node.kind == NodeKind::Separator)
+        .map(|part| {
+            let literal: Option<String> = part
+                .iter()
+                .map(|node| match node.kind {
+                    NodeKind::Literal(c) => Some(c),
+                    _ => None,
+                })
+                .collect();
+
+            match (part, literal) {
+                ([Node { kind: NodeKind::Star { double: true }, .. }], _) => Segment::DoubleStar,
+                (_, Some(literal)) => Segment::Literal(literal),
+                _ => Segment::Wildcard(part.to_vec()),
+            }
+        })
+        .collect()
+}
+
+/// Segments for a gitignore-style pattern, which CODEOWNERS and .gitignore share.
+///
+/// A pattern is anchored to the root when it has a slash anywhere but at its end; otherwise it
+/// matches at any depth. A trailing slash only matches directories, i.e. paths with something below
+/// them. With `descend` set, a pattern matching a directory also matches everything inside it.
+pub(crate) fn gitignore_style_segments(
+    pattern: &str,
+    base_offset: usize,
+    syntax: Syntax,
+    descend: bool,
+) -> Vec<Segment> {
+    let (body, dir_only) = match pattern.strip_suffix('/') {
+        Some(rest) => (rest, true),
+        None => (pattern, false),
+    };
+    let (body, offset, anchored) = match body.strip_prefix('/') {
+        Some(rest) => (rest, base_offset + 1, true),
+        None => (body, base_offset, body.contains('/')),
+    };
+
+    let mut segments = Vec::new();
+    if !anchored {
+        segments.push(Segment::DoubleStar);
+    }
+    segments.extend(split_segments(&parse(body, offset, syntax)));
+
+    // A trailing "/**" matches everything inside the directory, but not the directory itself. Node
+    // offsets count characters, not bytes.
+    if segments.len() > 1 && matches!(segments.last(), Some(Segment::DoubleStar)) {
+        let any_name = Node { kind: NodeKind::Star { double: false }, offset: offset + body.chars().count() - 2 };
+        segments.insert(segments.len() - 1, Segment::Wildcard(vec![any_name]));
+    }
+
+    if dir_only {
+        let any_name =
+            Node { kind: NodeKind::Star { double: false }, offset: base_offset + pattern.chars().count() - 1 };
+        segments.push(Segment::Wildcard(vec![any_name]));
+    }
+    if descend || dir_only {
+        segments.push(Segment::DoubleStar);
+    }
+
+    segments
+}
@@ -0,0 +1,168 @@
+// The `on:` section of a GitHub Actions workflow file.
+// See https://docs.github.com/en/actions/reference/workflows-and-actions/workflow-syntax#on
+
+use crate::{yaml, Glob, SourcePattern};
+
+// The events whose runs depend on the changed files
+pub(crate) const PATH_EVENTS: &[&str] = &["push", "pull_request", "pull_request_target"];
+
+/// The triggers of a workflow file, with the filter patterns of each event.
+#[derive(Debug, Clone)]
+pub struct Workflow {
+    /// The `name:` of the workflow, if it has one
+    pub name: Option<String>,
+    pub events: Vec<WorkflowEvent>,
+}
+
+impl Workflow {
+    pub fn event(&self, name: &str) -> Option<&WorkflowEvent> {
+        self.events.iter().find(|event| event.name == name)
+    }
+}
+
+/// An event under `on:`, such as `push` or `pull_request`, and its filters.
+///
+/// A filter that isn't written is `None`, which is different from an empty list: `paths: []`
+/// never matches a change.
+#[derive(Debug, Clone, Default)]
+pub struct WorkflowEvent {
+    pub name: String,
+    pub branches: Option<Vec<SourcePattern>>,
+    pub branches_ignore: Option<Vec<SourcePattern>>,
+    pub tags: Option<Vec<SourcePattern>>,
+    pub tags_ignore: Option<Vec<SourcePattern>>,
+    pub paths: Option<Vec<SourcePattern>>,
+    pub paths_ignore: Option<Vec<SourcePattern>>,
+}
+
+impl WorkflowEvent {
+    /// The filters that are written, keyed by their name in the file.
+    pub fn filters(&self) -> Vec<(&'static str, &[SourcePattern])> {
+        [
+            ("branches", &self.branches),
+            ("branches-ignore", &self.branches_ignore),
+            ("tags", &self.tags),
+            ("tags-ignore", &self.tags_ignore),
+            ("paths", &self.paths),
+            ("paths-ignore", &self.paths_ignore),
+        ]
+        .into_iter()
+        .filter_map(|(key, patterns)| patterns.as_deref().map(|patterns| (key, patterns)))
+        .collect()
+    }
+
+    /// The `paths` and `paths-ignore` filters that are written, keyed by their name in the file.
+    pub fn path_filters(&self) -> Vec<(&'static str, &[SourcePattern])> {
+        [("paths", &self.paths), ("paths-ignore", &self.paths_ignore)]
+            .into_iter()
+            .filter_map(|(key, patterns)| patterns.as_deref().map(|patterns| (key, patterns)))
+            .collect()
+    }
+
+    /// Whether a change to these files runs the workflow, judging from the path filters alone.
+    ///
+    /// With `paths`, at least one file has to be included by the filter; with `paths-ignore`, at
+    /// least one file has to escape it. GitHub rejects workflows that use both for an event, in
+    /// which case `paths` is the one applied. Paths can be given as strings or as bytes, like the
+    /// ones [`read_git_index_bytes`](crate::read_git_index_bytes) lists.
+    pub fn is_triggered_by<P: AsRef<[u8]>>(&self, changed_paths: &[P]) -> bool {
+        changed_paths.iter().any(|path| self.decide_bytes(path.as_ref()).triggers)
+    }
+
+    /// How the path filters treat a change to a single file, and which pattern decided.
+    pub fn decide(&self, path: &str) -> PathDecision {
+        self.decide_bytes(path.as_bytes())
+    }
+
+    pub fn decide_bytes(&self, path: &[u8]) -> PathDecision {
+        let (filter, patterns) = match (&self.paths, &self.paths_ignore) {
+            (Some(paths), _) => ("paths", paths),
+            (None, Some(paths_ignore)) => ("paths-ignore", paths_ignore),
+            (None, None) => return PathDecision { triggers: true, filter: None, pattern_index: None },
+        };
+
+        // The last matching pattern decides, like in a Filter
+        let pattern_index = patterns.iter().rposition(|pattern| pattern.glob.is_match_bytes(path));
+        let included = pattern_index.is_some_and(|idx| !patterns[idx].glob.is_negation());
+        let triggers = if filter == "paths" { included } else { !included };
+
+        PathDecision { triggers, filter: Some(filter), pattern_index }
+    }
+
+    /// Whether a push to (or a pull request against) the branch runs the workflow, judging from the
+    /// branch and tag filters alone.
+    ///
+    /// An event that only filters tags doesn't run for branches at all.
+    pub fn runs_for_branch(&self, branch: &str) -> bool {
+        let included = |patterns: &[SourcePattern]| {
+            patterns
+                .iter()
+                .rposition(|pattern| pattern.glob.is_match(branch))
+                .is_some_and(|idx| !patterns[idx].glob.is_negation())
+        };
+
+        if let Some(branches) = &self.branches {
+            included(branches)
+        } else if let Some(branches_ignore) = &self.branches_ignore {
+            !included(branches_ignore)
+        } else {
+            self.tags.is_none() && self.tags_ignore.is_none()
+        }
+    }
+}
+
+/// How an event's path filters treat a changed file, see [`WorkflowEvent::decide`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PathDecision {
+    /// Whether a change to the file alone runs the workflow
+    pub triggers: bool,
+    /// The filter that was applied, "paths" or "paths-ignore", or `None` if the event has neither
+    pub filter: Option<&'static str>,
+    /// The index in that filter of the last pattern matching the file, which decided
+    pub pattern_index: Option<usize>,
+}
+
+/// Extracts the events and their filters from the content of a workflow file.
+///
+/// `on:` can be a single event name, a list of names or a mapping of events to their settings.
+/// Anything else in the file is ignored.
+pub fn parse_workflow(content: &str) -> Workflow {
+    let document = yaml::parse(content);
+    let name = document.get("name").and_then(|name| name.as_scalar()).map(|name| name.value.clone());
+
+    let events = match document.get("on") {
+        Some(on) if !on.entries().is_empty() => on
+            .entries()
+            .iter()
+            .map(|(event, settings)| WorkflowEvent {
+                name: event.value.clone(),
+                branches: source_patterns(settings, "branches"),
+                branches_ignore: source_patterns(settings, "branches-ignore"),
+                tags: source_patterns(settings, "tags"),
+                tags_ignore: source_patterns(settings, "tags-ignore"),
+                paths: source_patterns(settings, "paths"),
+                paths_ignore: source_patterns(settings, "paths-ignore"),
+            })
+            .collect(),
+        Some(on) => on
+            .scalars()
+            .into_iter()
+            .map(|event| WorkflowEvent { name: event.value.clone(), ..WorkflowEvent::default() })
+            .collect(),
+        None => vec![],
+    };
+
+    Workflow { name, events }
+}
+
+fn source_patterns(settings: &yaml::Node, key: &str) -> Option<Vec<SourcePattern>> {
+    let patterns = settings.get(key)?;
+
+    Some(
+        patterns
+            .scalars()
+            .into_iter()
+            .map(|scalar| SourcePattern { glob: Glob::new(&scalar.value), line: scalar.line, column: scalar.column })
+            .collect(),
+    )
+}
@@ -0,0 +1,559 @@
+// A small, lenient YAML reader for CI configuration files.
+//
+// It covers the block and flow collections, quoting styles and block scalars those files use, and
+// keeps the position and raw text of every scalar so that findings can point at the source. It
+// never fails: text it doesn't understand is kept as a plain scalar, which is also how unquoted
+// patterns such as `**/README.md` (an alias to a real YAML parser) survive for linting.
+
+// How deeply flow collections can nest before the rest is kept as a plain scalar, so that the
+// recursive parser can't be made to overflow the stack
+const MAX_FLOW_DEPTH: usize = 128;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Value {
+    Null,
+    Scalar(Scalar),
+    Sequence(Vec<Node>),
+    Mapping(Vec<(Scalar, Node)>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Node {
+    pub(crate) value: Value,
+    // 1-based position of the node's first character
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Scalar {
+    // The value after unquoting and unescaping
+    pub(crate) value: String,
+    // The text as written, including quotes
+    pub(crate) raw: String,
+    pub(crate) style: ScalarStyle,
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+    // Whether the scalar was written inside a flow collection like `[a, b]`
+    pub(crate) in_flow: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ScalarStyle {
+    Plain,
+    SingleQuoted,
+    DoubleQuoted,
+    Block,
+}
+
+impl Node {
+    pub(crate) fn get(&self, key: &str) -> Option<&Node> {
+        match &self.value {
+            Value::Mapping(entries) => entries.iter().find(|(k, _)| k.value == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn entries(&self) -> &[(Scalar, Node)] {
+        match &self.value {
+            Value::Mapping(entries) => entries,
+            _ => &[],
+        }
+    }
+
+    pub(crate) fn items(&self) -> &[Node] {
+        match &self.value {
+            Value::Sequence(items) => items,
+            _ => &[],
+        }
+    }
+
+    pub(crate) fn as_scalar(&self) -> Option<&Scalar> {
+        match &self.value {
+            Value::Scalar(scalar) => Some(scalar),
+            _ => None,
+        }
+    }
+
+    /// The scalars of a sequence, or the node itself when a single scalar stands in for a list.
+    pub(crate) fn scalars(&self) -> Vec<&Scalar> {
+        match &self.value {
+            Value::Scalar(scalar) => vec![scalar],
+            Value::Sequence(items) => items.iter().filter_map(|item| item.as_scalar()).collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Every scalar below the node, mapping keys excepted, in document order.
+    pub(crate) fn descendant_scalars(&self) -> Vec<&Scalar> {
+        match &self.value {
+            Value::Scalar(scalar) => vec![scalar],
+            Value::Sequence(items) => items.iter().flat_map(|item| item.descendant_scalars()).collect(),
+            Value::Mapping(entries) => entries.iter().flat_map(|(_, value)| value.descendant_scalars()).collect(),
+            Value::Null => vec![],
+        }
+    }
+}
+
+pub(crate) fn parse(source: &str) -> Node {
+    let lines: Vec<Line> = source
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, raw)| {
+            let content = strip_comment(raw);
+            let indent = content.len() - content.trim_start_matches(' ').len();
+            let text = content.trim();
+            if text.is_empty() || text == "---" || text == "..." || text.starts_with('%') {
+                return None;
+            }
+            Some(Line { number: idx + 1, indent, text: text.to_string() })
+        })
+        .collect();
+
+    let raw_lines: Vec<&str> = source.lines().collect();
+    let mut parser = Parser { lines, raw_lines, pos: 0 };
+
+    match parser.lines.first() {
+        Some(first) => {
+            let indent = first.indent;
+            parser.parse_block(indent)
+        }
+        None => Node { value: Value::Null, line: 1, column: 1 },
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Line {
+    number: usize,
+    // 0-based column of the first character of `text`
+    indent: usize,
+    text: String,
+}
+
+struct Parser<'a> {
+    lines: Vec<Line>,
+    raw_lines: Vec<&'a str>,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn parse_block(&mut self, indent: usize) -> Node {
+        let line = match self.lines.get(self.pos) {
+            Some(line) if line.indent >= indent => line.clone(),
+            _ => return Node { value: Value::Null, line: 0, column: 0 },
+        };
+
+        if is_sequence_item(&line.text) {
+            self.parse_sequence(line.indent)
+        } else if split_key(&line.text).is_some() {
+            self.parse_mapping(line.indent)
+        } else {
+            self.pos += 1;
+            self.parse_value(&line.text, line.number, line.indent, line.indent.saturating_sub(1))
+        }
+    }
+
+    // A value written after "key:" or "- ", starting at the 0-based `column`
+    fn parse_value(&mut self, text: &str, line: usize, column: usize, parent_indent: usize) -> Node {
+        if is_block_scalar_header(text) {
+            self.parse_block_scalar(parent_indent, line, column)
+        } else if text.starts_with('[') || text.starts_with('{') {
+            self.parse_flow(text, line, column)
+        } else {
+            self.parse_inline(text, line, column)
+        }
+    }
+
+    fn parse_sequence(&mut self, indent: usize) -> Node {
+        let first = &self.lines[self.pos];
+        let (line, column) = (first.number, first.indent + 1);
+        let mut items = Vec::new();
+
+        while let Some(current) = self.lines.get(self.pos) {
+            if current.indent != indent || !is_sequence_item(&current.text) {
+                break;
+            }
+
+            let rest = current.text[1..].trim_start();
+            if rest.is_empty() {
+                self.pos += 1;
+                items.push(self.parse_nested(indent, false));
+            } else {
+                // Re-read the rest of the line as if it started a block of its own, so that
+                // "- key: value" continues with the keys on the following lines
+                let offset = current.text.len() - rest.len();
+                let rest_line =
+                    Line { number: current.number, indent: current.indent + offset, text: rest.to_string() };
+                self.lines[self.pos] = rest_line.clone();
+                items.push(self.parse_block(rest_line.indent));
+                self.skip_continuation_lines(indent);
+            }
+        }
+
+        Node { value: Value::Sequence(items), line, column }
+    }
+
+    fn parse_mapping(&mut self, indent: usize) -> Node {
+        let first = &self.lines[self.pos];
+        let (line, column) = (first.number, first.indent + 1);
+        let mut entries = Vec::new();
+
+        while let Some(current) = self.lines.get(self.pos).cloned() {
+            if current.indent != indent {
+                break;
+            }
+            let Some((key_text, rest_offset)) = split_key(&current.text) else {
+                break;
+            };
+
+            let key = parse_scalar(key_text, current.number, current.indent, false);
+            let rest = current.text[rest_offset..].trim_start();
+            let rest_column = current.indent + current.text[..current.text.len() - rest.len()].chars().count();
+            self.pos += 1;
+
+            let value = if rest.is_empty() {
+                self.parse_nested(indent, true)
+            } else {
+                let value = self.parse_value(rest, current.number, rest_column, indent);
+                self.skip_continuation_lines(indent);
+                value
+            };
+
+            entries.push((key, value));
+        }
+
+        Node { value: Value::Mapping(entries), line, column }
+    }
+
+    // Lines indented deeper than a finished value continue a multi-line plain scalar
+    fn skip_continuation_lines(&mut self, indent: usize) {
+        while self.lines.get(self.pos).is_some_and(|next| next.indent > indent) {
+            self.pos += 1;
+        }
+    }
+
+    // The value on the lines after "key:" or "-"
+    fn parse_nested(&mut self, parent_indent: usize, allow_same_indent_sequence: bool) -> Node {
+        match self.lines.get(self.pos) {
+            Some(next) if next.indent > parent_indent => {
+                let indent = next.indent;
+                self.parse_block(indent)
+            }
+            // A mapping value may be a sequence at the same indentation as its key
+            Some(next)
+                if allow_same_indent_sequence && next.indent == parent_indent && is_sequence_item(&next.text) =>
+            {
+                self.parse_sequence(parent_indent)
+            }
+            _ => Node { value: Value::Null, line: 0, column: 0 },
+        }
+    }
+
+    // "|" and ">" scalars span the following lines that are indented deeper than the key
+    fn parse_block_scalar(&mut self, indent: usize, line: usize, column: usize) -> Node {
+        let mut end_line = line;
+        while let Some(next) = self.lines.get(self.pos) {
+            if next.indent <= indent {
+                break;
+            }
+            end_line = next.number;
+            self.pos += 1;
+        }
+
+        let content: Vec<&str> = self.raw_lines[line..end_line].iter().map(|raw| raw.trim()).collect();
+        let value = content.join("\n");
+        let scalar = Scalar {
+            value: value.clone(),
+            raw: value,
+            style: ScalarStyle::Block,
+            line,
+            column: column + 1,
+            in_flow: false,
+        };
+        Node { value: Value::Scalar(scalar), line, column: column + 1 }
+    }
+
+    // Flow collections may continue on the following lines until their brackets balance
+    fn parse_flow(&mut self, first: &str, line: usize, column: usize) -> Node {
+        let mut chars: Vec<(char, usize, usize)> =
+            first.chars().enumerate().map(|(idx, c)| (c, line, column + idx + 1)).collect();
+
+        while !is_balanced(&chars) {
+            let Some(next) = self.lines.get(self.pos) else {
+                break;
+            };
+            chars.push((' ', next.number, 0));
+            chars.extend(next.text.chars().enumerate().map(|(idx, c)| (c, next.number, next.indent + idx + 1)));
+            self.pos += 1;
+        }
+
+        let mut cursor = 0;
+        parse_flow_value(&chars, &mut cursor, 0)
+    }
+
+    fn parse_inline(&mut self, text: &str, line: usize, column: usize) -> Node {
+        let scalar = parse_scalar(text, line, column, false);
+        let (line, column) = (scalar.line, scalar.column);
+        Node { value: Value::Scalar(scalar), line, column }
+    }
+}
+
+fn parse_flow_value(chars: &[(char, usize, usize)], cursor: &mut usize, depth: usize) -> Node {
+    skip_spaces(chars, cursor);
+    let Some(&(c, line, column)) = chars.get(*cursor) else {
+        return Node { value: Value::Null, line: 0, column: 0 };
+    };
+
+    match c {
+        '[' | '{' if depth == MAX_FLOW_DEPTH => {
+            let scalar = nested_flow_scalar(chars, cursor);
+            Node { line: scalar.line, column: scalar.column, value: Value::Scalar(scalar) }
+        }
+        '[' => {
+            *cursor += 1;
+            let mut items = Vec::new();
+            loop {
+                skip_spaces(chars, cursor);
+                match chars.get(*cursor) {
+                    None => break,
+                    // A mismatched closer ends the collection as well, so that it is always consumed
+                    Some((']' | '}', ..)) => {
+                        *cursor += 1;
+                        break;
+                    }
+                    Some((',', ..)) => *cursor += 1,
+                    Some(_) => items.push(parse_flow_value(chars, cursor, depth + 1)),
+                }
+            }
+            Node { value: Value::Sequence(items), line, column }
+        }
+        '{' => {
+            *cursor += 1;
+            let mut entries = Vec::new();
+            loop {
+                skip_spaces(chars, cursor);
+                match chars.get(*cursor) {
+                    None => break,
+                    // A mismatched closer ends the collection as well, so that it is always consumed
+                    Some((']' | '}', ..)) => {
+                        *cursor += 1;
+                        break;
+                    }
+                    Some((',', ..)) => *cursor += 1,
+                    Some(_) => {
+                        let key = flow_scalar(chars, cursor, true);
+                        skip_spaces(chars, cursor);
+                        let value = if chars.get(*cursor).map(|(c, ..)| *c) == Some(':') {
+                            *cursor += 1;
+                            parse_flow_value(chars, cursor, depth + 1)
+                        } else {
+                            Node { value: Value::Null, line: key.line, column: key.column }
+                        };
+                        entries.push((key, value));
+                    }
+                }
+            }
+            Node { value: Value::Mapping(entries), line, column }
+        }
+        _ => {
+            let scalar = flow_scalar(chars, cursor, false);
+            Node { line: scalar.line, column: scalar.column, value: Value::Scalar(scalar) }
+        }
+    }
+}
+
+// Reads a scalar inside a flow collection, ending at the next unquoted separator
+fn flow_scalar(chars: &[(char, usize, usize)], cursor: &mut usize, is_key: bool) -> Scalar {
+    let start = *cursor;
+    let mut quote: Option<char> = None;
+
+    while let Some(&(c, ..)) = chars.get(*cursor) {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if *cursor == start && (c == '\'' || c == '"') => quote = Some(c),
+            None if c == ',' || c == ']' || c == '}' => break,
+            None if is_key && c == ':' => break,
+            None => {}
+        }
+        *cursor += 1;
+    }
+
+    let text: String = chars[start..*cursor].iter().map(|(c, ..)| *c).collect();
+    let (_, line, column) = chars[start];
+    let mut scalar = parse_scalar(text.trim_end(), line, column - 1, true);
+    scalar.in_flow = true;
+    scalar
+}
+
+// Reads a collection nested too deeply to parse as a scalar, ending after its closing bracket
+fn nested_flow_scalar(chars: &[(char, usize, usize)], cursor: &mut usize) -> Scalar {
+    let start = *cursor;
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+
+    while let Some(&(c, ..)) = chars.get(*cursor) {
+        *cursor += 1;
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '\'' | '"' => quote = Some(c),
+                '[' | '{' => depth += 1,
+                ']' | '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+
+    let text: String = chars[start..*cursor].iter().map(|(c, ..)| *c).collect();
+    let (_, line, column) = chars[start];
+    Scalar { value: text.clone(), raw: text, style: ScalarStyle::Plain, line, column, in_flow: true }
+}
+
+fn skip_spaces(chars: &[(char, usize, usize)], cursor: &mut usize) {
+    while chars.get(*cursor).is_some_and(|(c, ..)| c.is_whitespace()) {
+        *cursor += 1;
+    }
+}
+
+fn is_balanced(chars: &[(char, usize, usize)]) -> bool {
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+
+    for (c, ..) in chars {
+        match quote {
+            Some(q) if *c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '\'' | '"' => quote = Some(*c),
+                '[' | '{' => depth += 1,
+                ']' | '}' => depth -= 1,
+                _ => {}
+            },
+        }
+    }
+
+    depth <= 0
+}
+
+// `column` is the 0-based column where `text` starts
+fn parse_scalar(text: &str, line: usize, column: usize, in_flow: bool) -> Scalar {
+    // Anchors and tags in front of a value don't change what we are interested in
+    let mut text = text;
+    let mut column = column;
+    while text.starts_with('&') || text.starts_with("!!") {
+        match text.find(' ') {
+            Some(space) => {
+                let rest = text[space..].trim_start();
+                column += text[..text.len() - rest.len()].chars().count();
+                text = rest;
+            }
+            None => break,
+        }
+    }
+
+    let (value, style) = if let Some(inner) = text.strip_prefix('\'').and_then(|rest| rest.strip_suffix('\'')) {
+        (inner.replace("''", "'"), ScalarStyle::SingleQuoted)
+    } else if let Some(inner) =
+        text.strip_prefix('"').and_then(|rest| rest.strip_suffix('"')).filter(|_| text.len() > 1)
+    {
+        (unescape_double_quoted(inner), ScalarStyle::DoubleQuoted)
+    } else {
+        (text.to_string(), ScalarStyle::Plain)
+    };
+
+    Scalar { value, raw: text.to_string(), style, line, column: column + 1, in_flow }
+}
+
+fn unescape_double_quoted(inner: &str) -> String {
+    let mut value = String::new();
+    let mut chars = inner.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => value.push('\n'),
+            Some('t') => value.push('\t'),
+            Some('0') => value.push('\0'),
+            Some(other) => value.push(other),
+            None => value.push('\\'),
+        }
+    }
+
+    value
+}
+
+fn is_sequence_item(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+fn is_block_scalar_header(text: &str) -> bool {
+    (text.starts_with('|') || text.starts_with('>'))
+        && text[1..].chars().all(|c| c == '-' || c == '+' || c.is_ascii_digit())
+}
+
+// Finds the "key:" separator outside quotes and flow brackets, returning the key text and the
+// offset of the value that follows it
+fn split_key(text: &str) -> Option<(&str, usize)> {
+    let mut quote: Option<char> = None;
+    let mut depth = 0;
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+
+    for (i, (byte_idx, c)) in chars.iter().enumerate() {
+        match quote {
+            Some(q) if *c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '\'' | '"' if i == 0 => quote = Some(*c),
+                '[' | '{' => depth += 1,
+                ']' | '}' => depth -= 1,
+                ':' if depth == 0 => {
+                    let next = chars.get(i + 1).map(|(_, c)| *c);
+                    if next.is_none() || next == Some(' ') || next == Some('\t') {
+                        return Some((text[..*byte_idx].trim_end(), byte_idx + 1));
+                    }
+                }
+                _ => {}
+            },
+        }
+
+        // Only a quoted key or a plain key may come before the separator
+        if i == 0 && (*c == '[' || *c == '{') {
+            return None;
+        }
+    }
+
+    None
+}
+
+// Comments start with a # at the beginning of the line or after whitespace, outside of quotes
+fn strip_comment(line: &str) -> &str {
+    let mut quote: Option<char> = None;
+    let mut previous = ' ';
+
+    for (idx, c) in line.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '\'' | '"' if previous == ' ' || previous == '[' || previous == ',' || previous == '-' => {
+                    quote = Some(c)
+                }
+                '#' if previous.is_whitespace() => return &line[..idx],
+                _ => {}
+            },
+        }
+        previous = c;
+    }
+
+    line
+}
@@ -0,0 +1,61 @@
+use pathglob::{AzurePathFilter, Dialect, Filter, Glob};
+
+fn assert_azure_match(pattern: &str, path: &str, expected: bool) {
+    let matches = Glob::with_dialect(pattern, Dialect::Azure).is_match(path);
+    assert_eq!(matches, expected, "Pattern '{}' vs '{}' -> {} (expected {})", pattern, path, matches, expected);
+}
+
+#[test]
+fn test_prefix_matching() {
+    assert_azure_match("docs", "docs/README.md", true);
+    assert_azure_match("docs", "docs/nested/file.md", true);
+    assert_azure_match("docs", "docs", true);
+    assert_azure_match("/docs/", "docs/README.md", true); // leading and trailing slashes are optional
+    assert_azure_match("docs", "documentation/file.md", false); // prefixes are whole folders
+    assert_azure_match("docs", "src/docs/file.md", false);
+}
+
+#[test]
+fn test_wildcards() {
+    assert_azure_match("src/app/**/myapp*", "src/app/a/b/myapp.cs", true);
+    assert_azure_match("src/app/**/myapp*", "src/app/myapp.cs", true);
+    assert_azure_match("src/*/tests", "src/core/tests/unit.cs", true);
+    assert_azure_match("src/*/tests", "src/core/nested/tests/unit.cs", false);
+    assert_azure_match("v?", "v1/notes.md", true);
+    assert_azure_match("v?", "v10/notes.md", false);
+}
+
+#[test]
+fn test_exclude_wins_regardless_of_order() {
+    let filter = AzurePathFilter::new(&["docs"], &["docs/README.md"]);
+    assert!(filter.is_triggered_by("docs/guide.md"));
+    assert!(!filter.is_triggered_by("docs/README.md"));
+    assert!(!filter.is_triggered_by("src/main.rs"));
+
+    // Same depth: the exclude wins
+    let filter = AzurePathFilter::new(&["docs"], &["docs"]);
+    assert!(!filter.is_triggered_by("docs/guide.md"));
+}
+
+#[test]
+fn test_deeper_include_overrides_exclude() {
+    let filter = AzurePathFilter::new(&[], &["tools"]);
+    assert!(filter.is_triggered_by("src/main.rs")); // no includes means the whole repository
+    assert!(!filter.is_triggered_by("tools/build.sh"));
+
+    let filter = AzurePathFilter::new(&["/", "tools/trigger-runs-on-these"], &["tools"]);
+    assert!(filter.is_triggered_by("tools/trigger-runs-on-these/run.sh"));
+    assert!(!filter.is_triggered_by("tools/other.sh"));
+}
+
+#[test]
+fn test_compare_with_github_filter() {
+    // The GitHub translation that looks equivalent re-includes README.md, because order matters there
+    let azure = AzurePathFilter::new(&["docs"], &["docs/README.md"]);
+    let github = Filter::new(&["!docs/README.md", "docs/**"]);
+
+    let paths = ["docs/guide.md", "docs/README.md", "src/main.rs"];
+    let differences: Vec<&str> =
+        paths.iter().copied().filter(|path| azure.is_triggered_by(path) != github.is_match(path)).collect();
+    assert_eq!(differences, ["docs/README.md"]);
+}
@@ -0,0 +1,86 @@
+use std::ffi::OsStr;
+use std::path::Path;
+
+use pathglob::{Dialect, Filter, Glob, Normalization};
+
+fn assert_bytes_match(glob: &Glob, path: &[u8], expected: bool) {
+    let matches = glob.is_match_bytes(path);
+    assert_eq!(
+        matches,
+        expected,
+        "Pattern '{}' vs {:?} -> {} (expected {})",
+        glob.pattern(),
+        String::from_utf8_lossy(path),
+        matches,
+        expected
+    );
+}
+
+#[test]
+fn test_valid_utf8() {
+    for (pattern, path) in [("src/**", "src/a/b.rs"), ("**.md", "docs/été.md"), ("[CB]at", "Bat"), ("*.jsx?", "a.js")]
+    {
+        assert_bytes_match(&Glob::new(pattern), path.as_bytes(), true);
+    }
+    assert_bytes_match(&Glob::new("src/*"), b"docs/a.md", false);
+}
+
+#[test]
+fn test_invalid_bytes_match_wildcards() {
+    assert_bytes_match(&Glob::new("src/*.rs"), b"src/\xff\xfe.rs", true);
+    assert_bytes_match(&Glob::new("src/**"), b"src/\xc3/a.rs", true);
+    assert_bytes_match(&Glob::new("**.rs"), b"\x80/lib.rs", true);
+    assert_bytes_match(&Glob::new("**.rs"), b"lib.r\xff", false);
+    // A lossy conversion would turn the byte into U+FFFD and match
+    assert_bytes_match(&Glob::new("caf\u{FFFD}"), b"caf\xff", false);
+    assert_bytes_match(&Glob::new("caf*"), b"caf\xff", true);
+    // Each invalid byte is a character of its own
+    let picomatch = Glob::with_dialect("a?b", Dialect::Picomatch { dot: false });
+    assert_bytes_match(&picomatch, b"a\xffb", true);
+    assert_bytes_match(&picomatch, b"a\xff\xfeb", false);
+    // A valid character after an invalid byte is still decoded
+    assert_bytes_match(&Glob::new("*é"), b"\xff\xc3\xa9", true);
+}
+
+#[test]
+fn test_invalid_bytes_in_classes() {
+    assert_bytes_match(&Glob::new("[a-z]*"), b"\xffa", false);
+    let gitignore = Glob::with_dialect("[!a-z]*.log", Dialect::Gitignore);
+    assert_bytes_match(&gitignore, b"logs/\xff.log", true);
+    assert_bytes_match(&gitignore, b"logs/x.log", false);
+}
+
+#[test]
+fn test_filter() {
+    let filter = Filter::new(&["src/**", "!**.md"]);
+    assert!(filter.is_match_bytes(b"src/\xff.rs"));
+    assert!(!filter.is_match_bytes(b"src/\xff.md"));
+    assert!(!filter.is_match_bytes(b"docs/\xff.rs"));
+    assert!(filter.is_match_path(Path::new("src/lib.rs")));
+    assert!(filter.is_match_os(OsStr::new("src/main.rs")));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_os_str() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let glob = Glob::new("docs/**.md");
+    assert!(glob.is_match_os(OsStr::from_bytes(b"docs/caf\xe9.md")));
+    assert!(glob.is_match_path(Path::new(OsStr::from_bytes(b"docs/\xe9t\xe9/index.md"))));
+    assert!(!glob.is_match_path(Path::new(OsStr::from_bytes(b"src/caf\xe9.md"))));
+}
+
+#[test]
+fn test_normalized_bytes() {
+    assert_eq!(Normalization::Normalize.apply_bytes(b"./src\\\xff.rs").as_deref(), Ok(b"src/\xff.rs".as_slice()));
+    // Only the valid parts are composed
+    assert_eq!(Normalization::Normalize.apply_bytes(b"\xffe\xcc\x81").as_deref(), Ok(b"\xff\xc3\xa9".as_slice()));
+
+    let error = Normalization::Strict.apply_bytes(b"src//\xff.rs").unwrap_err();
+    assert_eq!(error.to_string(), "src//\u{FFFD}.rs: repeated separator");
+
+    let glob = Glob::new("src/*.rs").with_normalization(Normalization::Normalize);
+    assert_bytes_match(&glob, b"./src//\xff.rs", true);
+    assert!(glob.try_match_bytes(b"../src/\xff.rs").is_err());
+}
@@ -0,0 +1,82 @@
+use pathglob::{Case, Dialect, Glob};
+
+fn assert_case_match(pattern: &str, case: Case, path: &str, expected: bool) {
+    let matches = Glob::new(pattern).with_case(case).is_match(path);
+    assert_eq!(
+        matches, expected,
+        "Pattern '{}' ({:?}) vs '{}' -> {} (expected {})",
+        pattern, case, path, matches, expected
+    );
+}
+
+#[test]
+fn test_sensitive_by_default() {
+    assert_eq!(Glob::new("docs/**").case(), Case::Sensitive);
+    assert_case_match("docs/**", Case::Sensitive, "Docs/a.md", false);
+    assert_case_match("*.MD", Case::Sensitive, "README.md", false);
+}
+
+#[test]
+fn test_ascii_insensitive() {
+    assert_case_match("docs/**", Case::AsciiInsensitive, "DOCS/Guide.md", true);
+    assert_case_match("*.md", Case::AsciiInsensitive, "README.MD", true);
+    assert_case_match("**.JS", Case::AsciiInsensitive, "src/app.js", true);
+    assert_case_match("readme.md", Case::AsciiInsensitive, "README.md", true);
+    assert_case_match("Octo*", Case::AsciiInsensitive, "octocat", true);
+    assert_case_match("Octo*", Case::AsciiInsensitive, "octo/cat", false);
+    // Optional and repeated characters
+    assert_case_match("*.jsX?", Case::AsciiInsensitive, "page.JSX", true);
+    assert_case_match("*.jsX?", Case::AsciiInsensitive, "page.JS", true);
+    assert_case_match("*a+.txt", Case::AsciiInsensitive, "bAAa.TXT", true);
+    // Only ASCII letters
+    assert_case_match("ÉTÉ/*", Case::AsciiInsensitive, "été/a", false);
+}
+
+#[test]
+fn test_unicode_insensitive() {
+    assert_case_match("ÉTÉ/*", Case::UnicodeInsensitive, "été/a", true);
+    assert_case_match("straße/*", Case::UnicodeInsensitive, "STRASSE/a", false);
+    assert_case_match("Σ*", Case::UnicodeInsensitive, "σοφία", true);
+    // The Kelvin sign lowercases to `k`
+    assert_case_match("\u{212A}*", Case::UnicodeInsensitive, "key", true);
+    assert_case_match("k*", Case::UnicodeInsensitive, "\u{212A}ey", true);
+}
+
+#[test]
+fn test_bracket_classes() {
+    assert_case_match("[A-C]at", Case::Sensitive, "bat", false);
+    assert_case_match("[A-C]*", Case::AsciiInsensitive, "bat", true);
+    assert_case_match("[a-c]*", Case::AsciiInsensitive, "Cat", true);
+    assert_case_match("[a-c]*", Case::AsciiInsensitive, "Dog", false);
+    assert_case_match("[xY]*", Case::AsciiInsensitive, "yes", true);
+    assert_case_match("[à-å]*", Case::AsciiInsensitive, "Ålesund", false);
+    assert_case_match("[à-å]*", Case::UnicodeInsensitive, "Ålesund", true);
+}
+
+#[test]
+fn test_other_dialects() {
+    let picomatch = Glob::with_dialect("src/*.{JS,TS}", Dialect::Picomatch { dot: false });
+    assert!(!picomatch.is_match("src/app.js"));
+    assert!(picomatch.with_case(Case::AsciiInsensitive).is_match("SRC/app.js"));
+
+    let gitignore = Glob::with_dialect("[!A-C]*.LOG", Dialect::Gitignore).with_case(Case::AsciiInsensitive);
+    assert!(gitignore.is_match("logs/debug.log"));
+    assert!(!gitignore.is_match("logs/bug.log"));
+
+    let gitlab = Glob::with_dialect("docs/**/*.md", Dialect::GitLab).with_case(Case::UnicodeInsensitive);
+    assert!(gitlab.is_match("Docs/Guide/INDEX.MD"));
+}
+
+#[test]
+fn test_could_match_under() {
+    let glob = Glob::new("docs/api/**").with_case(Case::AsciiInsensitive);
+    assert!(glob.could_match_under("DOCS"));
+    assert!(glob.could_match_under("Docs/API"));
+    assert!(!glob.could_match_under("src"));
+}
+
+#[test]
+fn test_regex_flag() {
+    assert_eq!(Glob::new("*.md").to_regex_string(), "^[^/]*\\.md$");
+    assert_eq!(Glob::new("*.md").with_case(Case::AsciiInsensitive).to_regex_string(), "(?i)^[^/]*\\.md$");
+}
@@ -0,0 +1,320 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use pathglob::run_cli;
+
+mod common;
+use common::TempDir;
+
+const CI: &str = "name: CI
+on:
+  push:
+    branches: [main]
+    paths:
+      - 'srcs/**'
+      - src/**
+      - '!src/**.md'
+  pull_request:
+    paths-ignore: [docs/**]
+";
+
+// Creates a repository without git metadata, so that every file on disk counts as tracked
+fn create_repo(name: &str, workflow: &str) -> TempDir {
+    let root =
+        TempDir::with_files(&format!("cli-{}", name), &["README.md", "docs/index.md", "src/lib.rs", "src/notes.md"]);
+    root.write(".github/workflows/ci.yml", workflow);
+    root
+}
+
+fn run(args: &[&str]) -> (ExitCode, String) {
+    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+    let mut out = Vec::new();
+    let code = run_cli(&args, &mut out);
+    (code, String::from_utf8(out).unwrap())
+}
+
+#[test]
+fn test_match() {
+    let (code, out) = run(&["match", "src/**", "!**.md", "--", "src/a.rs", "src/b.md", "README"]);
+    assert_eq!(code, ExitCode::FAILURE);
+    assert_eq!(
+        out,
+        "src/a.rs: included by pattern 0 `src/**`\nsrc/b.md: excluded by pattern 1 `!**.md`\nREADME: not matched\n"
+    );
+
+    let (code, _) = run(&["match", "--dialect", "gitignore", "*.log", "--", "logs/a.log"]);
+    assert_eq!(code, ExitCode::SUCCESS);
+}
+
+#[test]
+fn test_match_normalized_paths() {
+    let (code, out) = run(&["match", "--normalize", "src/*.rs", "--", "./src//a.rs", "src\\b.rs", "../c.rs"]);
+    assert_eq!(code, ExitCode::FAILURE);
+    assert_eq!(
+        out,
+        concat!(
+            "./src//a.rs: included by pattern 0 `src/*.rs`\n",
+            "src\\b.rs: included by pattern 0 `src/*.rs`\n",
+            "../c.rs: error: `..` segments can't be matched\n",
+        )
+    );
+
+    let (code, out) = run(&["match", "--strict-paths", "--format", "json", "src/*.rs", "--", "src/a.rs/"]);
+    assert_eq!(code, ExitCode::FAILURE);
+    assert!(
+        out.ends_with(
+            r#""results":[{"path":"src/a.rs/","included":false,"deciding_pattern":null,"error":"trailing separator"}]}
+"#
+        ),
+        "{}",
+        out
+    );
+}
+
+#[test]
+fn test_explain_json() {
+    let (_, out) = run(&["explain", "--format", "json", "src/**", "!**.md", "--", "src/b.md", "x"]);
+    assert_eq!(
+        out,
+        concat!(
+            r#"{"version":1,"command":"explain","#,
+            r#""patterns":[{"index":0,"pattern":"src/**","negation":false,"errors":[]},"#,
+            r#"{"index":1,"pattern":"!**.md","negation":true,"errors":[]}],"#,
+            r#""results":[{"path":"src/b.md","included":false,"deciding_pattern":1,"steps":["#,
+            r#"{"pattern":0,"matched":true,"effect":"include"},{"pattern":1,"matched":true,"effect":"exclude"}]},"#,
+            r#"{"path":"x","included":false,"deciding_pattern":null,"steps":["#,
+            r#"{"pattern":0,"matched":false,"effect":null},{"pattern":1,"matched":false,"effect":null}]}]}"#,
+            "\n"
+        )
+    );
+}
+
+#[test]
+fn test_which_workflows() {
+    let repo = create_repo("which", CI);
+    let repo = repo.to_str().unwrap();
+
+    let (code, out) = run(&["which-workflows", "--repo", repo, "src/lib.rs"]);
+    assert_eq!(code, ExitCode::SUCCESS);
+    assert_eq!(
+        out,
+        "ci.yml on.push: src/lib.rs included by paths[1] `src/**` at line 7\n\
+         ci.yml on.pull_request: src/lib.rs matches no pattern of paths-ignore\n"
+    );
+
+    let (code, out) = run(&["which-workflows", "--repo", repo, "--branch", "dev", "docs/index.md"]);
+    assert_eq!((code, out.as_str()), (ExitCode::FAILURE, ""));
+
+    let (_, out) = run(&["which-workflows", "--repo", repo, "--format", "json", "src/notes.md"]);
+    assert!(out.starts_with(r#"{"version":1,"command":"which-workflows","paths":["src/notes.md"],"branch":null,"#));
+    assert!(out.contains(
+        r#"{"event":"push","triggered":false,"runs_for_branch":true,"decisions":[{"path":"src/notes.md","triggers":false,"filter":"paths","deciding_pattern":{"index":2,"pattern":"!src/**.md","line":8,"column":9}}]}"#
+    ));
+    assert!(out.contains(r#""name":"CI""#));
+}
+
+#[test]
+fn test_lint() {
+    let repo = create_repo("lint", CI);
+    let workflow = repo.join(".github/workflows/ci.yml");
+
+    let (code, out) = run(&["lint", "--repo", repo.to_str().unwrap()]);
+    assert_eq!(code, ExitCode::FAILURE);
+    assert_eq!(
+        out,
+        format!(
+            "{}:6:9: `srcs/**` in on.push.paths: matches no tracked file; did you mean `src/**`?\n    srcs/**\n    ^^^^^^^\n",
+            workflow.display()
+        )
+    );
+
+    let (_, out) = run(&["lint", "--repo", repo.to_str().unwrap(), "--format", "json"]);
+    assert_eq!(
+        out,
+        format!(
+            concat!(
+                r#"{{"version":1,"command":"lint","findings":[{{"rule":"dead-pattern","severity":"warning","#,
+                r#""file":"{}","line":6,"column":9,"event":"push","filter":"paths","index":0,"pattern":"srcs/**","#,
+                r#""span":{{"offset":0,"length":7}},"message":"matches no tracked file","suggestions":["src/**"],"fixed":false}}]}}"#,
+                "\n"
+            ),
+            workflow.display()
+        )
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_lint_matches_names_that_are_not_utf8() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::Path;
+
+    let repo = create_repo("bytes", "on:\n  push:\n    paths: ['docs/*.md', 'docs/\u{fffd}.md']\n");
+    repo.write(Path::new("docs").join(OsStr::from_bytes(b"\xe2\x82.md")), "");
+    let repo = repo.to_str().unwrap();
+
+    // The cut-short euro sign is matched as it is, not as the U+FFFD a lossy conversion would give
+    let (code, out) = run(&["lint", "--repo", repo]);
+    assert_eq!(code, ExitCode::FAILURE);
+    assert!(out.contains("`docs/\u{fffd}.md` in on.push.paths: matches no tracked file"), "{}", out);
+    let (_, out) = run(&["matrix", "--repo", repo, "--format", "csv"]);
+    assert!(out.contains("docs/\u{fffd}.md,file,1,1\n"), "{}", out);
+}
+
+#[test]
+fn test_lint_fix() {
+    let repo = create_repo("fix", "on:\n  push:\n    paths:\n      - ./src/**\n      - *.md\n");
+    let repo = repo.to_str().unwrap();
+
+    let (code, out) = run(&["lint", "--repo", repo, "--fix", "--format", "json"]);
+    assert_eq!(code, ExitCode::SUCCESS);
+    assert!(out.contains(r#""rule":"yaml-quoting","severity":"error""#));
+    assert!(out.contains(r#""rule":"pattern-fix","severity":"warning""#));
+    assert!(!out.contains(r#""fixed":false"#));
+
+    let fixed = fs::read_to_string(PathBuf::from(repo).join(".github/workflows/ci.yml")).unwrap();
+    assert_eq!(fixed, "on:\n  push:\n    paths:\n      - src/**\n      - '*.md'\n");
+    assert_eq!(run(&["lint", "--repo", repo]), (ExitCode::SUCCESS, String::new()));
+}
+
+#[test]
+fn test_lint_fix_leaves_block_scalars() {
+    // The pattern isn't on the line its position points at, so only the second one is replaced
+    let content = "on:\n  push:\n    paths:\n      - >-\n        ./docs/\n      - ./src/\n";
+    let repo = create_repo("fix_block", content);
+    let repo = repo.to_str().unwrap();
+
+    let (code, out) = run(&["lint", "--repo", repo, "--fix"]);
+    assert_eq!(code, ExitCode::FAILURE);
+    assert!(out.contains("; use `docs/**`\n"), "{}", out);
+    assert!(out.contains("; replaced with `src/**`\n"), "{}", out);
+
+    let fixed = fs::read_to_string(PathBuf::from(repo).join(".github/workflows/ci.yml")).unwrap();
+    assert_eq!(fixed, content.replace("- ./src/", "- src/**"));
+}
+
+#[test]
+fn test_other_commands_json() {
+    let repo = create_repo("json", CI);
+    let repo = repo.to_str().unwrap();
+
+    let (_, out) = run(&["coverage", "--repo", repo, "--format", "json"]);
+    assert!(out.starts_with(r#"{"version":1,"command":"coverage","branch":"main","directories":[{"dir":"","#));
+
+    let (_, out) = run(&["matrix", "--repo", repo, "--format", "json", "--depth", "1"]);
+    assert!(out.starts_with(r#"{"version":1,"command":"matrix","workflows":["ci.yml"],"rows":["#));
+
+    let (code, _) = run(&["matrix", "--repo", repo, "--format", "yaml"]);
+    assert_eq!(code, ExitCode::from(2));
+
+    // The interactive commands have no JSON output, and say so before reading any input
+    for command in ["repl", "lsp"] {
+        assert_eq!(run(&[command, "--repo", repo, "--format", "json"]), (ExitCode::from(2), String::new()));
+    }
+}
+
+#[test]
+fn test_lint_github_annotations() {
+    let repo = create_repo("annotations", "on:\n  push:\n    paths:\n      - *.md\n      - srcs/**\n");
+    let repo = repo.to_str().unwrap();
+
+    let (code, out) = run(&["lint", "--repo", repo, "--format", "github"]);
+    assert_eq!(code, ExitCode::FAILURE);
+    let file = format!("{}/.github/workflows/ci.yml", repo).replace(':', "%3A").replace(',', "%2C");
+    assert_eq!(
+        out,
+        format!(
+            concat!(
+                "::error file={0},line=4,col=9,endColumn=13,title=pathglob yaml-quoting::",
+                "`*.md` in on.push.paths: unquoted, a pattern starting with `*` is read as a YAML alias; ",
+                "quote it as '*.md'\n",
+                "::warning file={0},line=5,col=9,endColumn=16,title=pathglob dead-pattern::",
+                "`srcs/**` in on.push.paths: matches no tracked file; did you mean `src/**`?\n"
+            ),
+            file
+        )
+    );
+
+    let (code, out) = run(&["lint", "--repo", repo, "--format", "github", "--fix"]);
+    assert_eq!(code, ExitCode::FAILURE);
+    assert!(out.starts_with("::notice file="));
+}
+
+#[test]
+fn test_lint_sarif() {
+    let repo = create_repo("sarif", "on:\n  push:\n    paths: ['srcs/**', '*.md']\n");
+
+    let (code, out) = run(&["lint", "--repo", repo.to_str().unwrap(), "--format", "sarif"]);
+    assert_eq!(code, ExitCode::FAILURE);
+    assert!(out.starts_with(r#"{"$schema":"https://json.schemastore.org/sarif-2.1.0.json","version":"2.1.0","runs":[{"tool":{"driver":{"name":"pathglob","version":"#));
+    assert!(out.contains(r#"{"id":"dead-pattern","name":"DeadPathPattern","shortDescription":{"text":"Path pattern matches no tracked file"},"#));
+    assert!(out.contains(concat!(
+        r#""results":[{"ruleId":"dead-pattern","ruleIndex":3,"level":"warning","#,
+        r#""message":{"text":"`srcs/**` in on.push.paths: matches no tracked file; did you mean `src/**`?"},"#,
+        r#""locations":[{"physicalLocation":{"artifactLocation":{"uri":".github/workflows/ci.yml","uriBaseId":"%SRCROOT%"},"#,
+        r#""region":{"startLine":3,"startColumn":13,"endColumn":22}}}],"#,
+        r#""properties":{"event":"push","filter":"paths","index":0,"pattern":"srcs/**","suggestions":["src/**"],"fixed":false}}]"#
+    )));
+}
+
+#[test]
+fn test_lint_shadowed_patterns() {
+    let repo =
+        create_repo("shadowed", "on:\n  push:\n    paths:\n      - '!docs/**'\n      - src/lib.rs\n      - src/**\n");
+    let repo = repo.to_str().unwrap();
+
+    let (code, out) = run(&["lint", "--repo", repo, "--format", "github"]);
+    assert_eq!(code, ExitCode::FAILURE);
+    let file = PathBuf::from(repo).join(".github/workflows/ci.yml");
+    assert_eq!(
+        out,
+        format!(
+            concat!(
+                "::warning file={0},line=4,col=9,endColumn=19,title=pathglob shadowed-pattern::",
+                "`!docs/**` in on.push.paths: excludes nothing, as no pattern before it includes files\n",
+                "::warning file={0},line=5,col=9,endColumn=19,title=pathglob shadowed-pattern::",
+                "`src/lib.rs` in on.push.paths: every file it matches is then included by pattern 2 `src/**`\n",
+            ),
+            file.display()
+        )
+    );
+
+    let (_, out) = run(&["lint", "--repo", repo, "--format", "sarif"]);
+    assert!(out.contains(r#"{"id":"shadowed-pattern","name":"ShadowedPathPattern","shortDescription":{"text":"Path pattern has no effect where it is"},"#));
+    assert!(out.contains(r#"{"ruleId":"shadowed-pattern","ruleIndex":4,"level":"warning","#));
+    assert!(out.contains(r#""region":{"startLine":5,"startColumn":9,"endColumn":19}"#));
+}
+
+#[test]
+fn test_caret_diagnostics() {
+    let (code, out) = run(&["match", "docs/**", "file[ab*.txt", "--", "docs/a"]);
+    assert_eq!(code, ExitCode::SUCCESS);
+    assert_eq!(
+        out,
+        "pattern 1: error: `[` is never closed, so the pattern can't match\n    file[ab*.txt\n        ^\ndocs/a: included by pattern 0 `docs/**`\n"
+    );
+
+    let repo = create_repo("caret", "on:\n  push:\n    branches: ['rel/[]*']\n    paths: ['?x*/**']\n");
+    let (code, out) = run(&["lint", "--repo", repo.to_str().unwrap()]);
+    assert_eq!(code, ExitCode::FAILURE);
+    let file = repo.join(".github/workflows/ci.yml");
+    assert_eq!(
+        out,
+        format!(
+            concat!(
+                "{0}:3:16: `rel/[]*` in on.push.branches: `[]` matches no character, so the pattern can't match\n",
+                "    rel/[]*\n",
+                "        ^^\n",
+                "{0}:4:13: `?x*/**` in on.push.paths: `?` makes the character before it optional, and there is none\n",
+                "    ?x*/**\n",
+                "    ^\n",
+                "{0}:4:13: `?x*/**` in on.push.paths: `?` makes the previous character optional in GitHub; `*` matches any characters; use `*x*/**`\n",
+                "    ?x*/**\n",
+                "    ^^^^^^\n"
+            ),
+            file.display()
+        )
+    );
+}
@@ -0,0 +1,76 @@
+use pathglob::{CodeOwners, Dialect, Glob};
+
+fn assert_owns(pattern: &str, path: &str, expected: bool) {
+    let matches = Glob::with_dialect(pattern, Dialect::Codeowners).is_match(path);
+    assert_eq!(matches, expected, "Pattern '{}' vs '{}' -> {} (expected {})", pattern, path, matches, expected);
+}
+
+#[test]
+fn test_unanchored_patterns() {
+    assert_owns("*", "README.md", true);
+    assert_owns("*", "deeply/nested/file.rs", true);
+    assert_owns("*.js", "app.js", true);
+    assert_owns("*.js", "src/web/app.js", true);
+    assert_owns("*.js", "app.ts", false);
+    assert_owns("apps/", "apps/main.go", true); // trailing slash: a directory anywhere
+    assert_owns("apps/", "src/apps/nested/main.go", true);
+    assert_owns("apps/", "apps", false); // a file named apps is not a directory
+}
+
+#[test]
+fn test_anchored_patterns() {
+    assert_owns("/build/logs/", "build/logs/today.log", true);
+    assert_owns("/build/logs/", "src/build/logs/today.log", false);
+    assert_owns("docs/*", "docs/getting-started.md", true);
+    assert_owns("docs/*", "docs/build-app/troubleshooting.md", false); // only files directly in docs
+    assert_owns("/apps/github", "apps/github/main.go", true); // a directory covers its contents
+    assert_owns("/apps/github", "apps/github", true);
+    assert_owns("/scripts/", "scripts/deploy.sh", true);
+}
+
+#[test]
+fn test_double_star() {
+    assert_owns("**/logs", "logs/a.log", true);
+    assert_owns("**/logs", "build/logs/a.log", true);
+    assert_owns("**/logs", "deeply/nested/logs", true);
+    assert_owns("docs/**/*.md", "docs/a/b.md", true);
+}
+
+#[test]
+fn test_special_characters_are_literal() {
+    assert_owns("file?.txt", "file1.txt", true); // ? is a single character, as in gitignore
+    assert_owns("[ab].txt", "a.txt", false);
+    assert_owns("[ab].txt", "[ab].txt", true);
+}
+
+#[test]
+fn test_last_match_wins() {
+    let codeowners = CodeOwners::parse(
+        "# Default owners\n\
+         *       @global-owner1 @global-owner2\n\
+         *.js    @js-owner #This is an inline comment.\n\
+         /build/logs/ @doctocat\n\
+         docs/*  docs@example.com\n\
+         /apps/ @octocat\n\
+         /apps/github\n",
+    );
+
+    assert!(codeowners.errors().is_empty(), "{:?}", codeowners.errors());
+    assert_eq!(codeowners.owners_of("README.md"), ["@global-owner1", "@global-owner2"]);
+    assert_eq!(codeowners.owners_of("src/app.js"), ["@js-owner"]);
+    assert_eq!(codeowners.owners_of("build/logs/app.js"), ["@doctocat"]);
+    assert_eq!(codeowners.owners_of("docs/index.md"), ["docs@example.com"]);
+    assert_eq!(codeowners.owners_of("apps/web/main.go"), ["@octocat"]);
+    assert!(codeowners.owners_of("apps/github/main.go").is_empty()); // ownership removed
+    assert_eq!(codeowners.rule_for("apps/github/main.go").unwrap().line, 7);
+}
+
+#[test]
+fn test_unsupported_lines_are_reported() {
+    let codeowners = CodeOwners::parse("!docs/ @octocat\n[ab].md @octocat\n*.rs not-an-owner\n*.md @docs-team\n");
+
+    let lines: Vec<usize> = codeowners.errors().iter().map(|error| error.line).collect();
+    assert_eq!(lines, [1, 2, 3]);
+    assert_eq!(codeowners.rules().len(), 1);
+    assert_eq!(codeowners.owners_of("docs/a.md"), ["@docs-team"]);
+}
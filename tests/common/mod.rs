@@ -0,0 +1,53 @@
+// Files on disk for the tests that read repositories. Each test binary only uses part of this.
+#![allow(dead_code)]
+
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// A fresh directory in the system's temporary directory, removed again when dropped, whether the
+/// test passed or not.
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// An empty directory named after the test process and `name`, which has to be unique within
+    /// the test binary.
+    pub fn new(name: &str) -> TempDir {
+        let path = std::env::temp_dir().join(format!("pathglob-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        TempDir { path }
+    }
+
+    /// A directory with the given files in it, all empty.
+    pub fn with_files(name: &str, files: &[&str]) -> TempDir {
+        let dir = TempDir::new(name);
+        for file in files {
+            dir.write(file, "");
+        }
+        dir
+    }
+
+    /// Writes a file below the directory, creating the directories leading to it.
+    pub fn write(&self, file: impl AsRef<Path>, content: impl AsRef<[u8]>) {
+        let path = self.path.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
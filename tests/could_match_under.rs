@@ -0,0 +1,68 @@
+use pathglob::{Dialect, Filter, Glob};
+
+fn assert_could_match(pattern: &str, dir_prefix: &str, expected: bool) {
+    let could_match = Glob::new(pattern).could_match_under(dir_prefix);
+    assert_eq!(
+        could_match, expected,
+        "Pattern '{}' under '{}' -> {} (expected {})",
+        pattern, dir_prefix, could_match, expected
+    );
+}
+
+#[test]
+fn test_literal_prefixes() {
+    assert_could_match("docs/**", "docs", true);
+    assert_could_match("docs/**", "docs/nested/deeply", true);
+    assert_could_match("docs/**", "src", false);
+    assert_could_match("docs/**", "src/docs", false); // docs must be at the root
+    assert_could_match("docs/*", "docs", true);
+    assert_could_match("docs/*", "docs/nested", false); // * doesn't descend
+    assert_could_match("docs/api/*.md", "docs", true);
+    assert_could_match("docs/api/*.md", "docs/guide", false);
+}
+
+#[test]
+fn test_root_level_patterns() {
+    assert_could_match("*.js", "", true);
+    assert_could_match("*.js", "src", false);
+    assert_could_match("README.md", "docs", false);
+    assert_could_match("docs", "docs", false); // the directory itself isn't below it
+}
+
+#[test]
+fn test_double_star_positions() {
+    assert_could_match("**", "anything/at/all", true);
+    assert_could_match("**/README.md", "src/deep", true);
+    assert_could_match("**.js", "src/deep", true);
+    assert_could_match("**/docs/*.md", "src/docs", true);
+    assert_could_match("src/**/test/*.rs", "src/a/b", true);
+    assert_could_match("src/**/test/*.rs", "lib/a/b", false);
+}
+
+#[test]
+fn test_wildcard_segments() {
+    assert_could_match("*src/**", "my-src", true);
+    assert_could_match("*src/**", "source", false);
+    assert_could_match("[CB]at/*", "Cat", true);
+    assert_could_match("[CB]at/*", "Dat", false);
+    assert_could_match("ab?/*", "a", true); // the optional character is expanded
+    assert_could_match("ab?/*", "ab", true);
+    assert_could_match("ab?/*", "abb", false);
+}
+
+#[test]
+fn test_other_dialects() {
+    let picomatch = Glob::with_dialect("**/*.yml", Dialect::Picomatch { dot: false });
+    assert!(picomatch.could_match_under("src/config"));
+    assert!(!picomatch.could_match_under(".github/workflows")); // ** doesn't enter dot directories
+
+    let codeowners = Glob::with_dialect("*.js", Dialect::Codeowners);
+    assert!(codeowners.could_match_under("src/web"));
+}
+
+#[test]
+fn test_filter_ignores_negations() {
+    let filter = Filter::new(&["docs/**", "!src/**"]);
+    assert!(filter.could_match_under("docs/guide"));
+    assert!(!filter.could_match_under("src"));
+}
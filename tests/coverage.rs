@@ -0,0 +1,73 @@
+use pathglob::{find_uncovered_files, parse_workflow};
+
+const FILES: &[&str] = &[
+    "Cargo.toml",
+    "README.md",
+    "docs/guide.md",
+    "docs/index.md",
+    "scripts/release.sh",
+    "src/lib.rs",
+    "src/generated/bindings.rs",
+];
+
+// (dir, uncovered files, file count) for every directory with uncovered files
+fn uncovered(workflows: &[&str], branch: &str) -> Vec<(String, Vec<String>, usize)> {
+    let workflows: Vec<_> = workflows.iter().map(|content| parse_workflow(content)).collect();
+
+    find_uncovered_files(&workflows, FILES, branch)
+        .into_iter()
+        .map(|directory| (directory.dir, directory.files, directory.file_count))
+        .collect()
+}
+
+fn group(dir: &str, files: &[&str], file_count: usize) -> (String, Vec<String>, usize) {
+    (dir.to_string(), files.iter().map(|file| file.to_string()).collect(), file_count)
+}
+
+const RUST: &str = "on:\n  push:\n    branches: [main]\n    paths: ['src/**', '!src/generated/**', Cargo.toml]\n";
+const DOCS: &str = "on:\n  push:\n    paths-ignore: ['src/**', Cargo.toml, 'scripts/**']\n";
+
+#[test]
+fn test_grouped_by_directory() {
+    let found = uncovered(&[RUST, DOCS], "main");
+    assert_eq!(
+        found,
+        [group("scripts", &["scripts/release.sh"], 1), group("src/generated", &["src/generated/bindings.rs"], 1)]
+    );
+    assert!(found.iter().all(|(_, files, file_count)| files.len() == *file_count));
+}
+
+#[test]
+fn test_branch_filters() {
+    // The Rust workflow only runs for main
+    let found = uncovered(&[RUST, DOCS], "develop");
+    assert_eq!(
+        found,
+        [
+            group("", &["Cargo.toml"], 2),
+            group("scripts", &["scripts/release.sh"], 1),
+            group("src", &["src/lib.rs"], 1),
+            group("src/generated", &["src/generated/bindings.rs"], 1),
+        ]
+    );
+}
+
+#[test]
+fn test_other_events() {
+    let pull_request_only = "on:\n  pull_request:\n    paths: ['**']\n";
+    assert_eq!(uncovered(&[pull_request_only], "main").len(), 5); // every directory
+
+    let every_push = "on: [push, pull_request]\n";
+    assert!(uncovered(&[pull_request_only, every_push], "main").is_empty());
+}
+
+#[test]
+fn test_is_fully_uncovered() {
+    let workflows = [parse_workflow("on:\n  push:\n    paths: ['docs/index.md']\n")];
+    let found = find_uncovered_files(&workflows, FILES, "main");
+
+    let docs = found.iter().find(|directory| directory.dir == "docs").unwrap();
+    assert_eq!(docs.files, ["docs/guide.md"]);
+    assert!(!docs.is_fully_uncovered());
+    assert!(found.iter().find(|directory| directory.dir == "src").unwrap().is_fully_uncovered());
+}
@@ -0,0 +1,51 @@
+use pathglob::{diff_workflows, parse_workflow};
+
+const FILES: &[&str] = &["Cargo.toml", "README.md", "docs/a.md", "src/lib.rs", "src/lib.test.rs"];
+
+// (event, included, excluded) for every changed event
+fn diff(old: &str, new: &str) -> Vec<(String, Vec<String>, Vec<String>)> {
+    diff_workflows(&parse_workflow(old), &parse_workflow(new), FILES)
+        .into_iter()
+        .map(|change| (change.event, change.included, change.excluded))
+        .collect()
+}
+
+fn change(event: &str, included: &[&str], excluded: &[&str]) -> (String, Vec<String>, Vec<String>) {
+    let to_strings = |files: &[&str]| files.iter().map(|file| file.to_string()).collect();
+    (event.to_string(), to_strings(included), to_strings(excluded))
+}
+
+#[test]
+fn test_paths_edit() {
+    let old = "on:\n  push:\n    paths: ['src/**', '!src/**.test.rs']\n";
+    let new = "on:\n  push:\n    paths: ['src/**', Cargo.toml]\n";
+    assert_eq!(diff(old, new), [change("push", &["Cargo.toml", "src/lib.test.rs"], &[])]);
+}
+
+#[test]
+fn test_switch_to_paths_ignore() {
+    let old = "on:\n  pull_request:\n    paths: ['docs/**']\n";
+    let new = "on:\n  pull_request:\n    paths-ignore: ['**.md']\n";
+    assert_eq!(
+        diff(old, new),
+        [change("pull_request", &["Cargo.toml", "src/lib.rs", "src/lib.test.rs"], &["docs/a.md"])]
+    );
+}
+
+#[test]
+fn test_added_and_removed_events() {
+    let old = "on:\n  push:\n    paths: ['**.md']\n  workflow_dispatch:\n";
+    let new = "on:\n  pull_request:\n    paths: ['docs/**']\n  schedule:\n    - cron: '0 0 * * *'\n";
+    assert_eq!(
+        diff(old, new),
+        [change("push", &[], &["README.md", "docs/a.md"]), change("pull_request", &["docs/a.md"], &[])]
+    );
+}
+
+#[test]
+fn test_unchanged() {
+    // Reordered and reformatted, but matching the same files
+    let old = "on:\n  push:\n    paths: ['src/**', 'docs/**']\n";
+    let new = "on:\n  push:\n    paths:\n      - docs/**\n      - src/*\n      - src/**\n";
+    assert!(diff(old, new).is_empty());
+}
@@ -0,0 +1,30 @@
+use pathglob::Filter;
+
+fn assert_filter_match(patterns: &[&str], path: &str, expected: bool) {
+    let matches = Filter::new(patterns).is_match(path);
+    assert_eq!(matches, expected, "Patterns {:?} vs '{}' -> {} (expected {})", patterns, path, matches, expected);
+}
+
+#[test]
+fn test_negation_excludes_earlier_matches() {
+    assert_filter_match(&["*.md", "!README.md"], "hello.md", true);
+    assert_filter_match(&["*.md", "!README.md"], "README.md", false);
+    assert_filter_match(&["*.md", "!README.md"], "docs/hello.md", false);
+}
+
+#[test]
+fn test_later_patterns_re_include() {
+    assert_filter_match(&["*.md", "!README.md", "README*"], "hello.md", true);
+    assert_filter_match(&["*.md", "!README.md", "README*"], "README.md", true);
+    assert_filter_match(&["*.md", "!README.md", "README*"], "README.doc", true);
+}
+
+#[test]
+fn test_deciding_glob() {
+    let filter = Filter::new(&["docs/**", "!docs/drafts/**", "docs/drafts/keep.md"]);
+    assert_eq!(filter.deciding_glob("docs/a.md"), Some(0));
+    assert_eq!(filter.deciding_glob("docs/drafts/a.md"), Some(1));
+    assert_eq!(filter.deciding_glob("docs/drafts/keep.md"), Some(2));
+    assert_eq!(filter.deciding_glob("src/main.rs"), None);
+    assert!(!Filter::new(&[]).is_match("anything"));
+}
@@ -0,0 +1,108 @@
+use pathglob::{apply_fixes, find_fixes, fix_pattern, parse_workflow};
+
+fn assert_fix(pattern: &str, expected: &[&str]) {
+    let replacements = fix_pattern(pattern).map(|fix| fix.replacements);
+    let expected = Some(expected.iter().map(|replacement| replacement.to_string()).collect());
+    assert_eq!(replacements, expected, "Pattern '{}'", pattern);
+}
+
+fn assert_no_fix(pattern: &str) {
+    assert_eq!(fix_pattern(pattern), None, "Pattern '{}'", pattern);
+}
+
+#[test]
+fn test_leading_and_trailing_slashes() {
+    assert_fix("./src/**", &["src/**"]);
+    assert_fix("/src/**", &["src/**"]);
+    assert_fix("!./docs/*.md", &["!docs/*.md"]);
+    assert_fix("docs/", &["docs/**"]);
+    assert_fix("docs/**/", &["docs/**"]);
+    assert_fix("./docs/", &["docs/**"]);
+}
+
+#[test]
+fn test_backslashes() {
+    assert_fix("src\\app\\*.rs", &["src/app/*.rs"]);
+    assert_fix(".\\scripts\\", &["scripts/**"]);
+}
+
+#[test]
+fn test_braces() {
+    assert_fix("{src,tests}/**", &["src/**", "tests/**"]);
+    assert_fix("**.{js,ts}", &["**.js", "**.ts"]);
+    assert_fix("a{b,c{d,e}}", &["ab", "acd", "ace"]);
+    assert_fix("!{a,b}/**", &["!a/**", "!b/**"]);
+    assert_no_fix("{single}/**");
+    assert_fix("src/***/{a,b}", &["src/***/a", "src/***/b"]);
+}
+
+#[test]
+fn test_too_many_braces() {
+    // 2^20 patterns, more than the expansion limit
+    assert_no_fix(&"{a,b}".repeat(20));
+    assert_no_fix(&format!("./{}", "{a,b}".repeat(20)));
+}
+
+#[test]
+fn test_question_mark_as_any_character() {
+    assert_fix("?.txt", &["*.txt"]);
+    assert_fix("docs/?/index.md", &["docs/*/index.md"]);
+    assert_fix("file.??", &["file.*"]);
+    assert_fix("*.?s", &["*.*s"]);
+    assert_no_fix("colou?r.md"); // an intended optional letter
+}
+
+#[test]
+fn test_no_fix() {
+    assert_no_fix("src/**");
+    assert_no_fix("!docs/**/*.md");
+    assert_no_fix("**");
+}
+
+#[test]
+fn test_messages() {
+    let fix = fix_pattern("./{a,b}/").unwrap();
+    assert_eq!(fix.replacements, ["a/**", "b/**"]);
+    assert!(fix.message.contains("`./`") && fix.message.contains("trailing") && fix.message.contains("braces"));
+}
+
+#[test]
+fn test_apply_fixes() {
+    let content = "on:
+  push:
+    paths:
+      - \"./src/**\"
+      - docs/
+      - '{a,b}/**' # both
+      - ok/**
+    paths-ignore: [x\\y, '/z', '?.md']
+  pull_request:
+    paths: '{c,d}'
+";
+    let expected = "on:
+  push:
+    paths:
+      - \"src/**\"
+      - docs/**
+      - 'a/**'
+      - 'b/**' # both
+      - ok/**
+    paths-ignore: [x/y, 'z', '*.md']
+  pull_request:
+    paths: ['c', 'd']
+";
+    let fixes = find_fixes(&parse_workflow(content));
+    assert_eq!(fixes.len(), 7);
+    assert_eq!(apply_fixes(content, &fixes), expected);
+
+    // Fixing again finds nothing left to fix
+    assert!(find_fixes(&parse_workflow(expected)).is_empty());
+}
+
+#[test]
+fn test_apply_fixes_quotes_plain_scalars() {
+    let content = "on:\n  push:\n    paths:\n      - ?.txt\n    paths-ignore: [{a,b}.md]\n";
+    let fixed = apply_fixes(content, &find_fixes(&parse_workflow(content)));
+    // Unquoted, `{a,b}.md` is a flow mapping to YAML rather than a pattern, so it is left alone
+    assert_eq!(fixed, "on:\n  push:\n    paths:\n      - '*.txt'\n    paths-ignore: [{a,b}.md]\n");
+}
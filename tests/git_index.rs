@@ -0,0 +1,192 @@
+use std::fs;
+use std::io;
+
+use pathglob::{parse_git_index, parse_git_index_bytes, read_git_index};
+
+mod common;
+use common::TempDir;
+
+const REGULAR_FILE: u32 = 0o100644;
+const SPARSE_DIRECTORY: u32 = 0o040000;
+
+struct Entry<'a> {
+    name: &'a [u8],
+    mode: u32,
+    stage: u16,
+    extended: bool,
+}
+
+fn file(name: &str) -> Entry<'_> {
+    Entry { name: name.as_bytes(), mode: REGULAR_FILE, stage: 0, extended: false }
+}
+
+// Builds an index file the way git writes it, without extensions or a trailing checksum
+fn build_index(version: u32, entries: &[Entry], hash_len: usize) -> Vec<u8> {
+    let mut data = b"DIRC".to_vec();
+    data.extend_from_slice(&version.to_be_bytes());
+    data.extend_from_slice(&(entries.len() as u32).to_be_bytes());
+
+    let mut previous_name: &[u8] = b"";
+    for entry in entries {
+        let start = data.len();
+        data.extend_from_slice(&[0; 24]); // ctime, mtime, dev, ino
+        data.extend_from_slice(&entry.mode.to_be_bytes());
+        data.extend_from_slice(&[0; 12]); // uid, gid, size
+        data.extend_from_slice(&vec![0xab; hash_len]);
+
+        let mut flags = (entry.name.len().min(0xfff) as u16) | (entry.stage << 12);
+        if entry.extended {
+            flags |= 0x4000;
+        }
+        data.extend_from_slice(&flags.to_be_bytes());
+        if entry.extended {
+            data.extend_from_slice(&0x2000u16.to_be_bytes()); // intent-to-add
+        }
+
+        if version == 4 {
+            let common = previous_name.iter().zip(entry.name).take_while(|(a, b)| a == b).count();
+            data.push((previous_name.len() - common) as u8); // small enough for a single varint byte
+            data.extend_from_slice(&entry.name[common..]);
+            data.push(0);
+        } else {
+            data.extend_from_slice(entry.name);
+            data.push(0);
+            while (data.len() - start) % 8 != 0 {
+                data.push(0);
+            }
+        }
+        previous_name = entry.name;
+    }
+
+    data
+}
+
+// An EWAH bitmap of up to 64 bits: a run-length word announcing one literal word, then that word
+fn ewah_bitmap(bits: &[usize], bit_count: u32) -> Vec<u8> {
+    let word = bits.iter().fold(0u64, |word, bit| word | 1 << bit);
+    let mut data = bit_count.to_be_bytes().to_vec();
+    data.extend_from_slice(&2u32.to_be_bytes());
+    data.extend_from_slice(&(1u64 << 33).to_be_bytes());
+    data.extend_from_slice(&word.to_be_bytes());
+    data.extend_from_slice(&0u32.to_be_bytes()); // position of the run-length word
+    data
+}
+
+#[test]
+fn test_versions() {
+    let entries = [file("README.md"), file("docs/guide.md"), file("docs/index.md"), file("src/main.rs")];
+    let expected = ["README.md", "docs/guide.md", "docs/index.md", "src/main.rs"];
+
+    for version in 2..=4 {
+        let paths = parse_git_index(&build_index(version, &entries, 20), 20).unwrap();
+        assert_eq!(paths, expected, "version {}", version);
+    }
+}
+
+#[test]
+fn test_extended_flags() {
+    let entries =
+        [file("a.txt"), Entry { name: b"new.txt", mode: REGULAR_FILE, stage: 0, extended: true }, file("z.txt")];
+
+    for version in 3..=4 {
+        let paths = parse_git_index(&build_index(version, &entries, 20), 20).unwrap();
+        assert_eq!(paths, ["a.txt", "new.txt", "z.txt"], "version {}", version);
+    }
+    assert!(parse_git_index(&build_index(2, &entries, 20), 20).is_err());
+}
+
+#[test]
+fn test_conflicts() {
+    let entries = [
+        Entry { name: b"conflict.txt", mode: REGULAR_FILE, stage: 1, extended: false },
+        Entry { name: b"conflict.txt", mode: REGULAR_FILE, stage: 2, extended: false },
+        Entry { name: b"conflict.txt", mode: REGULAR_FILE, stage: 3, extended: false },
+        file("src/lib.rs"),
+    ];
+
+    let paths = parse_git_index(&build_index(4, &entries, 20), 20).unwrap();
+    assert_eq!(paths, ["conflict.txt", "src/lib.rs"]);
+}
+
+#[test]
+fn test_sparse_directories() {
+    // The files below a sparse directory aren't listed, so the index can't be read
+    let entries = [
+        file("README.md"),
+        Entry { name: b"sparse/", mode: SPARSE_DIRECTORY, stage: 0, extended: false },
+        file("src/lib.rs"),
+    ];
+
+    let error = parse_git_index(&build_index(4, &entries, 20), 20).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert!(error.to_string().contains("`sparse/`"), "{}", error);
+}
+
+#[test]
+fn test_sha256_and_long_names() {
+    let long_name = format!("{}/file.txt", "d".repeat(5000));
+    let entries = [file(&long_name), file("short.txt")];
+
+    let paths = parse_git_index(&build_index(2, &entries, 32), 32).unwrap();
+    assert_eq!(paths, [long_name.as_str(), "short.txt"]);
+}
+
+#[test]
+fn test_names_that_are_not_utf8() {
+    // A euro sign cut short, which a lossy conversion would turn into a single U+FFFD
+    let name: &[u8] = b"docs/\xe2\x82.md";
+    let entries = [file("README.md"), Entry { name, mode: REGULAR_FILE, stage: 0, extended: false }];
+
+    for version in 2..=4 {
+        let data = build_index(version, &entries, 20);
+        assert_eq!(parse_git_index_bytes(&data, 20).unwrap(), [b"README.md".as_slice(), name], "version {}", version);
+        assert_eq!(parse_git_index(&data, 20).unwrap(), ["README.md", "docs/\u{fffd}.md"], "version {}", version);
+    }
+}
+
+#[test]
+fn test_split_index() {
+    let root = TempDir::new("index-split");
+    let shared_index = format!(".git/sharedindex.{}", "11".repeat(20));
+    let shared = [file("a.txt"), file("b.txt"), file("c.txt"), file("d.txt")];
+    root.write(&shared_index, build_index(2, &shared, 20));
+
+    // Drops b.txt, replaces c.txt with an entry that has no name of its own and adds a.rs
+    let replacement = Entry { name: b"", mode: REGULAR_FILE, stage: 0, extended: false };
+    let mut index = build_index(2, &[replacement, file("a.rs")], 20);
+    let mut link = vec![0x11; 20];
+    link.extend(ewah_bitmap(&[1], 4));
+    link.extend(ewah_bitmap(&[2], 4));
+    index.extend_from_slice(b"link");
+    index.extend_from_slice(&(link.len() as u32).to_be_bytes());
+    index.extend(link);
+    index.extend_from_slice(&[0; 20]); // checksum
+    root.write(".git/index", &index);
+
+    assert_eq!(read_git_index(&root).unwrap(), ["a.rs", "a.txt", "c.txt", "d.txt"]);
+    // Without the shared index, most of the paths would be missing
+    assert!(parse_git_index(&index, 20).is_err());
+
+    fs::remove_file(root.join(shared_index)).unwrap();
+    assert!(read_git_index(&root).is_err());
+}
+
+#[test]
+fn test_invalid_data() {
+    assert!(parse_git_index(b"PACK\0\0\0\x02\0\0\0\0", 20).is_err());
+    assert!(parse_git_index(b"DIRC\0\0\0\x05\0\0\0\0", 20).is_err()); // unsupported version
+
+    let data = build_index(2, &[file("README.md")], 20);
+    assert!(parse_git_index(&data[..data.len() - 4], 20).is_err()); // truncated
+}
+
+#[test]
+fn test_read_git_index_follows_gitdir_files() {
+    let root = TempDir::new("index-gitdir");
+    root.write("worktree/.git", "gitdir: ../main.git/worktrees/wt\n");
+    root.write("main.git/worktrees/wt/commondir", "../..\n");
+    root.write("main.git/config", "[extensions]\n\tobjectFormat = sha256\n");
+    root.write("main.git/worktrees/wt/index", build_index(2, &[file("README.md")], 32));
+
+    assert_eq!(read_git_index(&root.join("worktree")).unwrap(), ["README.md"]);
+}
@@ -0,0 +1,97 @@
+use pathglob::{Dialect, Gitignore, Glob};
+
+fn assert_ignore_pattern(pattern: &str, path: &str, expected: bool) {
+    let matches = Glob::with_dialect(pattern, Dialect::Gitignore).is_match(path);
+    assert_eq!(matches, expected, "Pattern '{}' vs '{}' -> {} (expected {})", pattern, path, matches, expected);
+}
+
+fn assert_ignored(gitignore: &Gitignore, path: &str, expected: bool) {
+    let ignored = gitignore.is_ignored(path);
+    assert_eq!(ignored, expected, "'{}' ignored -> {} (expected {})", path, ignored, expected);
+}
+
+#[test]
+fn test_anchoring() {
+    assert_ignore_pattern("*.log", "debug.log", true);
+    assert_ignore_pattern("*.log", "logs/debug.log", true); // no slash: any depth
+    assert_ignore_pattern("/debug.log", "debug.log", true);
+    assert_ignore_pattern("/debug.log", "logs/debug.log", false);
+    assert_ignore_pattern("doc/frotz", "doc/frotz", true);
+    assert_ignore_pattern("doc/frotz", "a/doc/frotz", false); // a middle slash anchors too
+    assert_ignore_pattern("frotz/", "a/frotz/file", true);
+    assert_ignore_pattern("frotz/", "a/frotz", false); // directories only
+}
+
+#[test]
+fn test_double_star() {
+    assert_ignore_pattern("**/foo", "foo", true);
+    assert_ignore_pattern("**/foo/bar", "a/b/foo/bar", true);
+    assert_ignore_pattern("abc/**", "abc/x/y", true);
+    assert_ignore_pattern("abc/**", "abc", false);
+    assert_ignore_pattern("a/**/b", "a/b", true);
+    assert_ignore_pattern("a/**/b", "a/x/y/b", true);
+}
+
+#[test]
+fn test_wildcards_classes_and_escapes() {
+    assert_ignore_pattern("debug?.log", "debug0.log", true);
+    assert_ignore_pattern("debug[0-9].log", "debug7.log", true);
+    assert_ignore_pattern("debug[!01].log", "debug1.log", false);
+    assert_ignore_pattern("*.log", ".hidden.log", true); // no special treatment of dotfiles
+    assert_ignore_pattern("\\#notes", "#notes", true);
+    assert_ignore_pattern("\\!important", "!important", true);
+    assert!(!Glob::with_dialect("\\!important", Dialect::Gitignore).is_negation());
+    assert!(Glob::with_dialect("!important", Dialect::Gitignore).is_negation());
+}
+
+#[test]
+fn test_re_inclusion() {
+    let gitignore = Gitignore::parse("# build output\n*.log\n!important.log\n");
+    assert_ignored(&gitignore, "debug.log", true);
+    assert_ignored(&gitignore, "important.log", false);
+    assert_ignored(&gitignore, "logs/important.log", false);
+    assert_ignored(&gitignore, "src/main.rs", false);
+}
+
+#[test]
+fn test_parent_directory_exclusion() {
+    // A file can't be re-included once its parent directory is excluded
+    let gitignore = Gitignore::parse("build/\n!build/keep.txt\n");
+    assert_ignored(&gitignore, "build/keep.txt", true);
+
+    // Excluding the contents rather than the directory allows re-inclusion
+    let gitignore = Gitignore::parse("build/*\n!build/keep.txt\n");
+    assert_ignored(&gitignore, "build/keep.txt", false);
+    assert_ignored(&gitignore, "build/out.bin", true);
+    assert_ignored(&gitignore, "build/nested/out.bin", true);
+}
+
+#[test]
+fn test_trailing_spaces() {
+    let gitignore = Gitignore::parse("*.tmp   \nspace\\ \n");
+    assert_ignored(&gitignore, "a.tmp", true);
+    assert_ignored(&gitignore, "space ", true);
+    assert_ignored(&gitignore, "space", false);
+}
+
+#[test]
+fn test_nested_files() {
+    let mut gitignore = Gitignore::parse("*.gen.rs\n");
+    gitignore.add_file("crates/core", "/generated\n!keep.gen.rs\n");
+
+    assert_ignored(&gitignore, "crates/core/generated/a.rs", true);
+    assert_ignored(&gitignore, "generated/a.rs", false); // anchored to crates/core
+    assert_ignored(&gitignore, "crates/core/keep.gen.rs", false); // deeper files take precedence
+    assert_ignored(&gitignore, "crates/other/keep.gen.rs", true);
+    assert_eq!(gitignore.rules()[1].base, "crates/core");
+}
+
+#[test]
+fn test_offsets_count_characters() {
+    // The wildcards standing in for `/**` and a trailing `/` point into the pattern by character
+    for (pattern, offset) in [("é/**", 2), ("日本/", 2), ("!ü/**", 3)] {
+        let glob = Glob::with_dialect(pattern, Dialect::Gitignore);
+        let debug = format!("{:?}", glob);
+        assert!(debug.contains(&format!("Star {{ double: false }}, offset: {} }}", offset)), "{}", debug);
+    }
+}
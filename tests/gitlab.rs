@@ -0,0 +1,129 @@
+use pathglob::{parse_gitlab_ci, Dialect, Glob};
+
+fn assert_gitlab_match(pattern: &str, path: &str, expected: bool) {
+    let matches = Glob::with_dialect(pattern, Dialect::GitLab).is_match(path);
+    assert_eq!(matches, expected, "Pattern '{}' vs '{}' -> {} (expected {})", pattern, path, matches, expected);
+}
+
+#[test]
+fn test_fnmatch_wildcards() {
+    assert_gitlab_match("*.md", "README.md", true);
+    assert_gitlab_match("*.md", "docs/README.md", false); // FNM_PATHNAME: * doesn't match slash
+    assert_gitlab_match("file?.txt", "file1.txt", true);
+    assert_gitlab_match("file?.txt", "file.txt", false);
+    assert_gitlab_match("[a-c]*.rb", "b_spec.rb", true);
+    assert_gitlab_match("[!a-c]*.rb", "b_spec.rb", false);
+    assert_gitlab_match("*", ".gitlab-ci.yml", true); // FNM_DOTMATCH
+    assert_gitlab_match("\\*.md", "*.md", true);
+}
+
+#[test]
+fn test_double_star() {
+    assert_gitlab_match("docs/**/*", "docs/a.md", true);
+    assert_gitlab_match("docs/**/*", "docs/a/b/c.md", true);
+    assert_gitlab_match("**/*.js", "index.js", true);
+    assert_gitlab_match("**/*.js", "src/web/index.js", true);
+    assert_gitlab_match("docs/**", "docs/a.md", true);
+    assert_gitlab_match("docs/**", "docs/a/b.md", false); // only **/ spans directories
+}
+
+#[test]
+fn test_braces() {
+    assert_gitlab_match("{Dockerfile,docker/scripts/*}", "Dockerfile", true);
+    assert_gitlab_match("{Dockerfile,docker/scripts/*}", "docker/scripts/build.sh", true);
+    assert_gitlab_match("{Dockerfile,docker/scripts/*}", "docker/Dockerfile", false);
+    assert_gitlab_match("v{1..3}.md", "v2.md", false); // no sequences in Ruby braces
+    assert_gitlab_match("v{1..3}.md", "v{1..3}.md", true);
+}
+
+#[test]
+fn test_parse_gitlab_ci() {
+    let changes = parse_gitlab_ci(
+        r#"
+stages: [build, test]
+
+workflow:
+  rules:
+    - changes:
+        - "**/*.rs"
+
+build:
+  stage: build
+  script:
+    - cargo build
+  rules:
+    - if: $CI_PIPELINE_SOURCE == "merge_request_event"
+      changes:
+        paths:
+          - Dockerfile
+          - 'docker/**/*'
+        compare_to: refs/heads/main
+    - when: manual
+
+docs:
+  script: |
+    echo "building docs: #1"
+    make docs
+  only:
+    changes: [docs/**/*, "*.md"]
+  except:
+    changes:
+    - docs/drafts/**/*
+
+.template:
+  rules:
+    - changes: [template/**/*]
+
+pages:
+  script: make site
+  rules:
+    - changes: [site/**/*]
+"#,
+    );
+
+    let summary: Vec<(String, String, Vec<String>)> = changes
+        .iter()
+        .map(|entry| {
+            let patterns = entry.patterns.iter().map(|pattern| pattern.glob.pattern().to_string()).collect();
+            (entry.job.clone(), entry.context.clone(), patterns)
+        })
+        .collect();
+
+    let expected = [
+        ("workflow", "rules[0]", vec!["**/*.rs"]),
+        ("build", "rules[0]", vec!["Dockerfile", "docker/**/*"]),
+        ("docs", "only", vec!["docs/**/*", "*.md"]),
+        ("docs", "except", vec!["docs/drafts/**/*"]),
+        ("pages", "rules[0]", vec!["site/**/*"]),
+    ];
+    let expected: Vec<(String, String, Vec<String>)> = expected
+        .iter()
+        .map(|(job, context, patterns)| {
+            (job.to_string(), context.to_string(), patterns.iter().map(|pattern| pattern.to_string()).collect())
+        })
+        .collect();
+    assert_eq!(summary, expected);
+
+    let docker = &changes[1].patterns[1];
+    assert_eq!((docker.line, docker.column), (18, 13));
+    let markdown = &changes[2].patterns[1];
+    assert_eq!((markdown.line, markdown.column), (27, 26));
+}
+
+#[test]
+fn test_positions_count_characters() {
+    let changes = parse_gitlab_ci("jöb: {rules: [{changes: [src/**]}]}\n");
+    assert_eq!((changes[0].patterns[0].line, changes[0].patterns[0].column), (1, 26));
+
+    let changes = parse_gitlab_ci("jöb:\n  only:\n    changes: &pätterns src/**\n");
+    assert_eq!((changes[0].patterns[0].line, changes[0].patterns[0].column), (3, 24));
+}
+
+#[test]
+fn test_changes_trigger() {
+    let changes = parse_gitlab_ci("build:\n  rules:\n    - changes:\n        - src/**/*\n");
+
+    assert!(changes[0].is_triggered_by(&["README.md", "src/main.rs"]));
+    assert!(!changes[0].is_triggered_by(&["README.md"]));
+    assert_eq!(changes[0].patterns[0].glob.dialect(), Dialect::GitLab);
+}
@@ -0,0 +1,173 @@
+use pathglob::{
+    apply_quoting, find_dead_patterns, find_quoting_problems, find_shadowed_patterns, parse_workflow, QuotingProblem,
+};
+
+const FILES: &[&str] =
+    &[".github/workflows/release.yml", "Cargo.toml", "README.md", "docs/index.md", "src/lib.rs", "src/parser/mod.rs"];
+
+// (pattern, suggestion) for every dead pattern of the workflow
+fn dead_patterns(paths: &[&str]) -> Vec<(String, Option<String>)> {
+    let list: Vec<String> = paths.iter().map(|path| format!("      - '{}'\n", path)).collect();
+    let workflow = parse_workflow(&format!("on:\n  push:\n    paths:\n{}", list.concat()));
+
+    find_dead_patterns(&workflow, FILES)
+        .into_iter()
+        .map(|dead_pattern| (dead_pattern.pattern.glob.pattern().to_string(), dead_pattern.suggestion))
+        .collect()
+}
+
+fn assert_dead(pattern: &str, expected_suggestion: Option<&str>) {
+    let found = dead_patterns(&[pattern]);
+    let expected = vec![(pattern.to_string(), expected_suggestion.map(|suggestion| suggestion.to_string()))];
+    assert_eq!(found, expected, "Pattern '{}'", pattern);
+}
+
+// (index, message) for every shadowed pattern of the workflow
+fn shadowed_patterns(paths: &[&str]) -> Vec<(usize, String)> {
+    let list: Vec<String> = paths.iter().map(|path| format!("      - '{}'\n", path)).collect();
+    let workflow = parse_workflow(&format!("on:\n  push:\n    paths:\n{}", list.concat()));
+
+    find_shadowed_patterns(&workflow, FILES)
+        .into_iter()
+        .map(|shadowed_pattern| (shadowed_pattern.index, shadowed_pattern.message))
+        .collect()
+}
+
+#[test]
+fn test_live_patterns() {
+    assert!(dead_patterns(&["src/**", "**.md", "Cargo.toml", "!docs/**", ".github/workflows/*"]).is_empty());
+}
+
+#[test]
+fn test_typo_suggestions() {
+    assert_dead("srcs/**", Some("src/**"));
+    assert_dead("scr/**/*.rs", Some("src/**/*.rs"));
+    assert_dead("src/parsre/**", Some("src/parser/**"));
+    assert_dead("Cargo.tmol", Some("Cargo.toml"));
+    assert_dead("!dcos/**", Some("!docs/**"));
+    assert_dead(".github/workflow/*.yml", Some(".github/workflows/*.yml"));
+}
+
+#[test]
+fn test_no_suggestion() {
+    assert_dead("tests/**", None); // nothing close enough
+    assert_dead("src/**/*.py", None); // the wildcard part is what matches nothing
+    assert_dead("docs", None); // a directory, but the pattern only matches a file of that name
+}
+
+#[test]
+fn test_positions() {
+    let workflow = parse_workflow("on:\n  pull_request:\n    paths-ignore: [docs/**, 'srcs/**']\n");
+    let dead_patterns = find_dead_patterns(&workflow, FILES);
+
+    assert_eq!(dead_patterns.len(), 1);
+    assert_eq!(dead_patterns[0].event, "pull_request");
+    assert_eq!(dead_patterns[0].key, "paths-ignore");
+    assert_eq!((dead_patterns[0].pattern.line, dead_patterns[0].pattern.column), (3, 29));
+}
+
+const UNQUOTED: &str = "on:
+  push:
+    branches: [main, release/[0-9]*]
+    tags:
+      - !v1*
+    paths:
+      - *.md
+      - [ab]*.rs   # comment
+      - {a,b}
+      - 'src/**'
+      - \"!docs/**\"
+    paths-ignore: [*.txt, src/[ab]/**, ok]
+";
+
+#[test]
+fn test_quoting_problems() {
+    let problems: Vec<(usize, usize, String, String)> = find_quoting_problems(UNQUOTED)
+        .into_iter()
+        .map(|problem| (problem.line, problem.column, problem.text, problem.quoted))
+        .collect();
+
+    let expected = [
+        (3, 22, "release/[0-9]*", "'release/[0-9]*'"),
+        (5, 9, "!v1*", "'!v1*'"),
+        (7, 9, "*.md", "'*.md'"),
+        (8, 9, "[ab]*.rs", "'[ab]*.rs'"),
+        (9, 9, "{a,b}", "'{a,b}'"),
+        (12, 20, "*.txt", "'*.txt'"),
+        (12, 27, "src/[ab]/**", "'src/[ab]/**'"),
+    ];
+    let expected: Vec<(usize, usize, String, String)> = expected
+        .iter()
+        .map(|(line, column, text, quoted)| (*line, *column, text.to_string(), quoted.to_string()))
+        .collect();
+    assert_eq!(problems, expected);
+}
+
+#[test]
+fn test_quoting_messages() {
+    let problems = find_quoting_problems(UNQUOTED);
+    assert_eq!((problems[1].event.as_str(), problems[1].key.as_str()), ("push", "tags"));
+    assert!(problems[1].message.contains("tag"));
+    assert!(problems[2].message.contains("alias"));
+    assert!(problems[3].message.contains("flow sequence"));
+    assert!(problems[6].message.contains("end the pattern early"));
+}
+
+#[test]
+fn test_apply_quoting() {
+    let quoted = apply_quoting(UNQUOTED, &find_quoting_problems(UNQUOTED));
+    assert!(find_quoting_problems(&quoted).is_empty());
+
+    let workflow = parse_workflow(&quoted);
+    let push = workflow.event("push").unwrap();
+    let paths: Vec<&str> = push.paths.as_ref().unwrap().iter().map(|pattern| pattern.glob.pattern()).collect();
+    assert_eq!(paths, ["*.md", "[ab]*.rs", "{a,b}", "src/**", "!docs/**"]);
+    let ignored: Vec<&str> = push.paths_ignore.as_ref().unwrap().iter().map(|pattern| pattern.glob.pattern()).collect();
+    assert_eq!(ignored, ["*.txt", "src/[ab]/**", "ok"]);
+    assert!(quoted.contains("      - '[ab]*.rs'   # comment\n"));
+}
+
+#[test]
+fn test_apply_quoting_skips_positions_outside_the_file() {
+    let problem = find_quoting_problems(UNQUOTED).remove(0);
+    for (line, column) in [(0, problem.column), (problem.line, 0), (1000, 1)] {
+        let problem = QuotingProblem { line, column, ..problem.clone() };
+        assert_eq!(apply_quoting(UNQUOTED, &[problem]), UNQUOTED);
+    }
+}
+
+#[test]
+fn test_shadowed_patterns() {
+    assert!(shadowed_patterns(&["src/**", "!src/parser/**", "**.md", "!docs/**"]).is_empty());
+
+    assert_eq!(
+        shadowed_patterns(&["!docs/**", "**.md"]),
+        [(0, "excludes nothing, as no pattern before it includes files".to_string())]
+    );
+    assert_eq!(
+        shadowed_patterns(&["src/**", "!docs/**"]),
+        [(1, "excludes no file that the patterns before it include".to_string())]
+    );
+    assert_eq!(
+        shadowed_patterns(&["src/parser/**", "src/**"]),
+        [(0, "every file it matches is then included by pattern 1 `src/**`".to_string())]
+    );
+    assert_eq!(
+        shadowed_patterns(&["**.md", "!**"]),
+        [(0, "every file it matches is then excluded by pattern 1 `!**`".to_string())]
+    );
+    assert_eq!(
+        shadowed_patterns(&["*.md", "README.md", "!*.md"]),
+        [
+            (0, "every file it matches is then excluded by pattern 2 `!*.md`".to_string()),
+            (1, "every file it matches is then excluded by pattern 2 `!*.md`".to_string())
+        ]
+    );
+    // Two later patterns between them
+    assert_eq!(
+        shadowed_patterns(&["src/**", "src/*.rs", "src/parser/**"]),
+        [(0, "it has no effect on any of the files it matches".to_string())]
+    );
+    // Dead patterns are left to find_dead_patterns
+    assert!(shadowed_patterns(&["!tests/**", "src/**"]).is_empty());
+}
@@ -0,0 +1,205 @@
+use std::io::Cursor;
+use std::path::Path;
+
+use pathglob::run_lsp;
+
+mod common;
+use common::TempDir;
+
+// A repository without git metadata, so that every file on disk counts as tracked
+fn create_repo(name: &str) -> TempDir {
+    TempDir::with_files(
+        &format!("lsp-{}", name),
+        &["README.md", "src/lib.rs", "src/main.rs", ".github/workflows/ci.yml"],
+    )
+}
+
+fn workflow_uri(root: &Path) -> String {
+    format!("file://{}", root.join(".github/workflows/ci.yml").display())
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+// Sends the messages, framed with their headers, and returns the content of the server's messages
+fn run(messages: &[String]) -> Vec<String> {
+    serve(messages.iter().map(|message| format!("Content-Length: {}\r\n\r\n{}", message.len(), message)).collect())
+}
+
+fn serve(input: String) -> Vec<String> {
+    let mut output = Vec::new();
+    run_lsp(&mut Cursor::new(input), &mut output).unwrap();
+
+    let mut output = String::from_utf8(output).unwrap();
+    let mut contents = Vec::new();
+    while let Some((header, rest)) = output.split_once("\r\n\r\n") {
+        let length: usize = header.strip_prefix("Content-Length: ").unwrap().parse().unwrap();
+        contents.push(rest[..length].to_string());
+        output = rest[length..].to_string();
+    }
+    contents
+}
+
+fn did_open(uri: &str, text: &str) -> String {
+    format!(
+        r#"{{"jsonrpc":"2.0","method":"textDocument/didOpen","params":{{"textDocument":{{"uri":"{}","languageId":"yaml","version":1,"text":"{}"}}}}}}"#,
+        uri,
+        escape(text)
+    )
+}
+
+#[test]
+fn test_initialize_and_shutdown() {
+    let responses = run(&[
+        r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"capabilities":{}}}"#.to_string(),
+        r#"{"jsonrpc":"2.0","method":"initialized","params":{}}"#.to_string(),
+        r#"{"jsonrpc":"2.0","id":"two","method":"workspace/symbol","params":{"query":""}}"#.to_string(),
+        r#"{"jsonrpc":"2.0","id":3,"method":"shutdown"}"#.to_string(),
+        r#"{"jsonrpc":"2.0","method":"exit"}"#.to_string(),
+        r#"{"jsonrpc":"2.0","id":4,"method":"shutdown"}"#.to_string(),
+    ]);
+
+    assert_eq!(responses.len(), 3);
+    assert!(responses[0].starts_with(
+        r#"{"jsonrpc":"2.0","id":1,"result":{"capabilities":{"textDocumentSync":{"openClose":true,"change":1,"save":true},"hoverProvider":true,"#
+    ));
+    assert_eq!(responses[1], r#"{"jsonrpc":"2.0","id":"two","error":{"code":-32601,"message":"unknown method"}}"#);
+    assert_eq!(responses[2], r#"{"jsonrpc":"2.0","id":3,"result":null}"#);
+}
+
+#[test]
+fn test_oversized_messages() {
+    let shutdown = r#"{"jsonrpc":"2.0","id":1,"method":"shutdown"}"#;
+
+    // Nesting that would overflow a recursive parser is turned down, and so is content over 16 MiB
+    let responses = run(&["[".repeat(100_000), shutdown.to_string()]);
+    assert_eq!(
+        responses[0],
+        r#"{"jsonrpc":"2.0","id":null,"error":{"code":-32700,"message":"invalid JSON: nesting deeper than 128 levels at 128"}}"#
+    );
+    assert_eq!(responses[1], r#"{"jsonrpc":"2.0","id":1,"result":null}"#);
+
+    let length = (16 << 20) + 1;
+    let input = format!(
+        "Content-Length: {}\r\n\r\n{}Content-Length: {}\r\n\r\n{}",
+        length,
+        " ".repeat(length),
+        shutdown.len(),
+        shutdown
+    );
+    let responses = serve(input);
+    assert_eq!(
+        responses,
+        [
+            r#"{"jsonrpc":"2.0","id":null,"error":{"code":-32600,"message":"message too large"}}"#,
+            r#"{"jsonrpc":"2.0","id":1,"result":null}"#,
+        ]
+    );
+}
+
+#[test]
+fn test_diagnostics() {
+    let root = create_repo("diagnostics");
+    let uri = workflow_uri(&root);
+
+    let responses = run(&[
+        did_open(&uri, "on:\n  push:\n    paths:\n      - 'srcs/**'\n      - src/[ab*\n"),
+        format!(
+            r#"{{"jsonrpc":"2.0","method":"textDocument/didChange","params":{{"textDocument":{{"uri":"{}","version":2}},"contentChanges":[{{"text":"on: push\n"}}]}}}}"#,
+            uri
+        ),
+        r#"{"jsonrpc":"2.0","method":"textDocument/didOpen","params":{"textDocument":{"uri":"file:///tmp/notes.yml","text":"x"}}}"#
+            .to_string(),
+    ]);
+
+    assert_eq!(responses.len(), 2);
+    assert_eq!(
+        responses[0],
+        format!(
+            concat!(
+                r#"{{"jsonrpc":"2.0","method":"textDocument/publishDiagnostics","params":{{"uri":"{}","diagnostics":["#,
+                r#"{{"range":{{"start":{{"line":4,"character":8}},"end":{{"line":4,"character":16}}}},"severity":1,"#,
+                r#""code":"invalid-pattern","source":"pathglob","message":"`[` is never closed, so the pattern can't match"}},"#,
+                r#"{{"range":{{"start":{{"line":3,"character":8}},"end":{{"line":3,"character":17}}}},"severity":2,"#,
+                r#""code":"dead-pattern","source":"pathglob","message":"matches no tracked file; did you mean `src/**`?"}}]}}}}"#
+            ),
+            uri
+        )
+    );
+    assert_eq!(
+        responses[1],
+        format!(
+            r#"{{"jsonrpc":"2.0","method":"textDocument/publishDiagnostics","params":{{"uri":"{}","diagnostics":[]}}}}"#,
+            uri
+        )
+    );
+}
+
+#[test]
+fn test_hover() {
+    let root = create_repo("hover");
+    let uri = workflow_uri(&root);
+    let hover = |line: usize, character: usize| {
+        format!(
+            r#"{{"jsonrpc":"2.0","id":{0},"method":"textDocument/hover","params":{{"textDocument":{{"uri":"{1}"}},"position":{{"line":{0},"character":{2}}}}}}}"#,
+            line, uri, character
+        )
+    };
+
+    let responses = run(&[
+        did_open(&uri, "on:\n  push:\n    branches: [main]\n    paths: [src/**, 'docs/**']\n"),
+        hover(3, 12),
+        hover(3, 20),
+        hover(3, 11),
+        hover(2, 16),
+    ]);
+
+    assert_eq!(
+        responses[1],
+        concat!(
+            r#"{"jsonrpc":"2.0","id":3,"result":{"contents":{"kind":"markdown","#,
+            r#""value":"`src/**` matches 2 tracked files:\n\n- `src/lib.rs`\n- `src/main.rs`"},"#,
+            r#""range":{"start":{"line":3,"character":12},"end":{"line":3,"character":18}}}}"#
+        )
+    );
+    assert!(responses[2].contains(r#""value":"`docs/**` matches no tracked file""#));
+    assert_eq!(responses[3], r#"{"jsonrpc":"2.0","id":3,"result":null}"#);
+    // Branch patterns don't match files
+    assert_eq!(responses[4], r#"{"jsonrpc":"2.0","id":2,"result":null}"#);
+}
+
+#[test]
+fn test_quick_fixes() {
+    let root = create_repo("fixes");
+    let uri = workflow_uri(&root);
+    let code_action = |id: usize, line: usize| {
+        format!(
+            r#"{{"jsonrpc":"2.0","id":{0},"method":"textDocument/codeAction","params":{{"textDocument":{{"uri":"{1}"}},"range":{{"start":{{"line":{2},"character":0}},"end":{{"line":{2},"character":0}}}},"context":{{"diagnostics":[]}}}}}}"#,
+            id, uri, line
+        )
+    };
+
+    let responses = run(&[
+        did_open(&uri, "on:\n  push:\n    paths:\n      - *.md\n      - ./src/**\n      - srcs/**"),
+        code_action(1, 3),
+        code_action(2, 4),
+        code_action(3, 5),
+        code_action(4, 0),
+    ]);
+
+    assert!(responses[1].contains(concat!(
+        r#""title":"Quote as '*.md'","kind":"quickfix","isPreferred":true,"#,
+        r#""diagnostics":[{"range":{"start":{"line":3,"character":8},"end":{"line":3,"character":12}},"code":"yaml-quoting","source":"pathglob"}],"#
+    )));
+    assert!(responses[1].contains(
+        r#"[{"range":{"start":{"line":3,"character":0},"end":{"line":4,"character":0}},"newText":"      - '*.md'\n"}]"#
+    ));
+    assert!(responses[2].contains(r#""title":"Replace with `src/**`""#));
+    assert!(responses[2].contains(r#""newText":"      - src/**\n""#));
+    // The change reaches the end of a file without a final newline
+    assert!(responses[3].contains(
+        r#"[{"range":{"start":{"line":5,"character":0},"end":{"line":5,"character":15}},"newText":"      - src/**"}]"#
+    ));
+    assert_eq!(responses[4], r#"{"jsonrpc":"2.0","id":4,"result":[]}"#);
+}
@@ -0,0 +1,77 @@
+use pathglob::{parse_workflow, trigger_matrix, TriggerMatrix};
+
+const FILES: &[&str] = &["Cargo.toml", "docs-site/index.html", "docs/a.md", "docs/b.md", "src/lib.rs"];
+
+fn matrix(max_depth: Option<usize>) -> TriggerMatrix {
+    let rust = parse_workflow("on:\n  push:\n    paths: ['src/**', Cargo.toml]\n");
+    let docs = parse_workflow(
+        "on:\n  pull_request:\n    paths: ['docs/a.md', 'docs-site/**']\n  schedule:\n    - cron: '0 0 * * *'\n",
+    );
+    let nightly = parse_workflow("on:\n  schedule:\n    - cron: '0 0 * * *'\n");
+
+    trigger_matrix(&[("rust", &rust), ("docs", &docs), ("nightly", &nightly)], FILES, max_depth)
+}
+
+// (path, is_dir, file_count, triggered) for every row
+fn rows(matrix: &TriggerMatrix) -> Vec<(&str, bool, usize, Vec<usize>)> {
+    matrix.rows.iter().map(|row| (row.path.as_str(), row.is_dir, row.file_count, row.triggered.clone())).collect()
+}
+
+#[test]
+fn test_rows() {
+    let matrix = matrix(None);
+    assert_eq!(matrix.workflows, ["rust", "docs", "nightly"]);
+    assert_eq!(
+        rows(&matrix),
+        [
+            ("Cargo.toml", false, 1, vec![1, 0, 0]),
+            ("docs", true, 2, vec![0, 1, 0]),
+            ("docs/a.md", false, 1, vec![0, 1, 0]),
+            ("docs/b.md", false, 1, vec![0, 0, 0]),
+            ("docs-site", true, 1, vec![0, 1, 0]),
+            ("docs-site/index.html", false, 1, vec![0, 1, 0]),
+            ("src", true, 1, vec![1, 0, 0]),
+            ("src/lib.rs", false, 1, vec![1, 0, 0]),
+        ]
+    );
+}
+
+#[test]
+fn test_max_depth() {
+    let paths: Vec<String> = matrix(Some(1)).rows.into_iter().map(|row| row.path).collect();
+    assert_eq!(paths, ["Cargo.toml", "docs", "docs-site", "src"]);
+}
+
+#[test]
+fn test_csv() {
+    let csv = matrix(Some(1)).to_csv();
+    let expected = "path,kind,files,rust,docs,nightly\n\
+                    Cargo.toml,file,1,1,0,0\n\
+                    docs,directory,2,0,1,0\n\
+                    docs-site,directory,1,0,1,0\n\
+                    src,directory,1,1,0,0\n";
+    assert_eq!(csv, expected);
+
+    let workflow = parse_workflow("on: push\n");
+    let quoted = trigger_matrix(&[("a, \"b\"", &workflow)], &["x"], None).to_csv();
+    assert_eq!(quoted, "path,kind,files,\"a, \"\"b\"\"\"\nx,file,1,1\n");
+}
+
+#[test]
+fn test_markdown() {
+    let markdown = matrix(Some(2)).to_markdown();
+    let lines: Vec<&str> = markdown.lines().collect();
+
+    assert_eq!(lines[0], "| Path | rust | docs | nightly |");
+    assert_eq!(lines[1], "|---|---|---|---|");
+    assert_eq!(lines[2], "| `Cargo.toml` | ✓ |  |  |");
+    assert_eq!(lines[3], "| `docs/` |  | 1 of 2 |  |");
+    assert_eq!(lines[6], "| `docs-site/` |  | all |  |");
+}
+
+#[test]
+fn test_json() {
+    let json = matrix(Some(1)).to_json();
+    assert!(json.starts_with("{\"workflows\":[\"rust\",\"docs\",\"nightly\"],\"rows\":["));
+    assert!(json.contains("{\"path\":\"docs\",\"kind\":\"directory\",\"files\":2,\"triggered\":[0,1,0]}"));
+}
@@ -0,0 +1,119 @@
+use pathglob::{Glob, Normalization};
+
+fn assert_normalized(normalization: Normalization, path: &str, expected: Result<&str, &str>) {
+    let result = normalization.apply(path);
+    let actual = result.as_deref().map_err(|error| error.message.as_str());
+    assert_eq!(actual, expected, "{:?} of '{}'", normalization, path);
+}
+
+#[test]
+fn test_off_by_default() {
+    assert_eq!(Glob::new("src/*.rs").normalization(), Normalization::Off);
+    assert!(!Glob::new("src/*.rs").is_match("./src/a.rs"));
+    assert_normalized(Normalization::Off, "src\\a.rs", Ok("src\\a.rs"));
+}
+
+#[test]
+fn test_normalize() {
+    let normalize = Normalization::Normalize;
+    assert_normalized(normalize, "src/a.rs", Ok("src/a.rs"));
+    assert_normalized(normalize, "./src/a.rs", Ok("src/a.rs"));
+    assert_normalized(normalize, "src/./a.rs", Ok("src/a.rs"));
+    assert_normalized(normalize, "src//a.rs", Ok("src/a.rs"));
+    assert_normalized(normalize, "src\\lib\\a.rs", Ok("src/lib/a.rs"));
+    assert_normalized(normalize, "docs/", Ok("docs"));
+    assert_normalized(normalize, ".\\docs\\\\", Ok("docs"));
+    assert_normalized(normalize, "", Ok(""));
+    assert_normalized(normalize, "src/../a.rs", Err("`..` segments can't be matched"));
+    assert_normalized(normalize, "/src/a.rs", Err("absolute paths can't be matched"));
+    assert_normalized(normalize, "\\src\\a.rs", Err("absolute paths can't be matched"));
+    assert_normalized(normalize, "C:\\src\\a.rs", Err("absolute paths can't be matched"));
+    assert_normalized(normalize, "c:/src/a.rs", Err("absolute paths can't be matched"));
+    assert_normalized(normalize, "src/c:/a.rs", Ok("src/c:/a.rs"));
+}
+
+#[test]
+fn test_unicode_composition() {
+    let normalize = Normalization::Normalize;
+    // As macOS writes file names
+    assert_normalized(normalize, "docs/cafe\u{301}.md", Ok("docs/café.md"));
+    assert_normalized(normalize, "Z\u{30C}lut\u{30C}ouc\u{30C}ky\u{301}", Ok("Žluťoučký"));
+    assert_normalized(normalize, "\u{1112}\u{1161}\u{11AB}\u{1100}\u{1173}\u{11AF}", Ok("한글"));
+    assert_normalized(normalize, "\u{438}\u{306}", Ok("й"));
+    assert_normalized(normalize, "\u{304B}\u{3099}", Ok("が"));
+    // Singletons are replaced, and marks are put in canonical order before composing
+    assert_normalized(normalize, "\u{212B}", Ok("Å"));
+    assert_normalized(normalize, "e\u{323}\u{302}", Ok("ệ"));
+    assert_normalized(normalize, "e\u{302}\u{323}", Ok("ệ"));
+    assert_normalized(normalize, "a\u{301}\u{323}", Ok("ạ\u{301}"));
+    // Accents without a composed form are kept
+    assert_normalized(normalize, "q\u{301}", Ok("q\u{301}"));
+    assert_normalized(normalize, "\u{301}a", Ok("\u{301}a"));
+}
+
+// Lines of NormalizationTest.txt from the UCD 14.0.0 the tables are generated from, with the source,
+// NFC, NFD, NFKC and NFKD columns
+const NORMALIZATION_TEST: &str = "\
+1E0A;1E0A;0044 0307;1E0A;0044 0307;
+1E0A 0323;1E0C 0307;0044 0323 0307;1E0C 0307;0044 0323 0307;
+0044 0307 0323;1E0C 0307;0044 0323 0307;1E0C 0307;0044 0323 0307;
+1E0A 031B 0323;1E0C 031B 0307;0044 031B 0323 0307;1E0C 031B 0307;0044 031B 0323 0307;
+212B;00C5;0041 030A;00C5;0041 030A;
+1E69;1E69;0073 0323 0307;1E69;0073 0323 0307;
+1100 AC00 11A8;1100 AC01;1100 1100 1161 11A8;1100 AC01;1100 1100 1161 11A8;
+0061 0315 0300 05AE 0300 0062;00E0 05AE 0300 0315 0062;0061 05AE 0300 0300 0315 0062;00E0 05AE 0300 0315 0062;0061 05AE 0300 0300 0315 0062;
+";
+
+#[test]
+fn test_normalization_test_lines() {
+    for line in NORMALIZATION_TEST.lines() {
+        let columns: Vec<String> = line
+            .split(';')
+            .take(5)
+            .map(|column| {
+                column.split(' ').map(|cp| char::from_u32(u32::from_str_radix(cp, 16).unwrap()).unwrap()).collect()
+            })
+            .collect();
+        // NFC of the source, NFC and NFD columns is the NFC column
+        for column in &columns[..3] {
+            assert_normalized(Normalization::Normalize, column, Ok(&columns[1]));
+        }
+    }
+}
+
+#[test]
+fn test_strict() {
+    let strict = Normalization::Strict;
+    assert_normalized(strict, "src/café.md", Ok("src/café.md"));
+    assert_normalized(strict, "src\\a.rs", Err("backslash separator"));
+    assert_normalized(strict, "./src/a.rs", Err("`.` segment"));
+    assert_normalized(strict, "src/", Err("trailing separator"));
+    assert_normalized(strict, "src//a.rs", Err("repeated separator"));
+    assert_normalized(strict, "cafe\u{301}", Err("not in Unicode NFC"));
+    for path in ["\u{438}\u{306}", "\u{304B}\u{3099}", "e\u{323}\u{302}", "\u{212B}", "\u{1E0D}\u{307}\u{323}"] {
+        assert_normalized(strict, path, Err("not in Unicode NFC"));
+    }
+    assert_normalized(strict, "\u{1E0B}\u{323}", Err("not in Unicode NFC"));
+    assert_normalized(strict, "\u{1E0D}\u{307}", Ok("\u{1E0D}\u{307}"));
+    assert_normalized(strict, "../a.rs", Err("`..` segments can't be matched"));
+}
+
+#[test]
+fn test_glob_matching() {
+    let glob = Glob::new("src/**/*.rs").with_normalization(Normalization::Normalize);
+    assert!(glob.is_match("./src//lib/a.rs"));
+    assert!(glob.is_match("src\\lib\\a.rs"));
+    assert!(!glob.is_match("src/../a.rs"));
+    assert!(glob.try_match("src/../a.rs").is_err());
+    assert!(Glob::new("docs/café.md").with_normalization(Normalization::Normalize).is_match("docs/cafe\u{301}.md"));
+
+    let strict = Glob::new("src/**").with_normalization(Normalization::Strict);
+    assert_eq!(strict.try_match("src/a.rs"), Ok(true));
+    assert_eq!(strict.try_match("docs/a.md"), Ok(false));
+    let error = strict.try_match("./src/a.rs").unwrap_err();
+    assert_eq!(error.to_string(), "./src/a.rs: `.` segment");
+    assert!(!strict.is_match("./src/a.rs"));
+    // Directory prefixes are cleaned up rather than rejected
+    assert!(strict.could_match_under("./src/"));
+    assert!(!strict.could_match_under("../src"));
+}
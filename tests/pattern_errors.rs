@@ -0,0 +1,58 @@
+use pathglob::{Dialect, Glob};
+
+// Asserts the (offset, length) spans of the errors found in a pattern
+fn assert_errors(pattern: &str, expected: &[(usize, usize)]) {
+    let glob = Glob::new(pattern);
+    let spans: Vec<(usize, usize)> = glob.errors().iter().map(|error| (error.offset, error.len)).collect();
+    assert_eq!(spans, expected, "Pattern '{}' -> {:?} (expected {:?})", pattern, glob.errors(), expected);
+}
+
+#[test]
+fn test_valid_patterns() {
+    assert_errors("src/**", &[]);
+    assert_errors("*.jsx?", &[]);
+    assert_errors("[CB]at/*.md", &[]);
+    assert_errors("a+b*", &[]);
+    assert_errors("!docs/**", &[]);
+    // Without wildcards the segment compares literally, brackets included
+    assert_errors("file[1].txt", &[]);
+    assert_errors("**[", &[]);
+    assert_errors("?foo", &[]);
+    assert!(Glob::new("?foo").is_match("?foo"));
+}
+
+#[test]
+fn test_unclosed_bracket() {
+    assert_errors("file[ab*.txt", &[(4, 1)]);
+    assert_errors("src/[ab/*.md", &[(4, 1)]);
+    assert_errors("!*[a", &[(2, 1)]);
+    assert_errors("[a]*[b", &[(4, 1)]);
+}
+
+#[test]
+fn test_empty_brackets() {
+    assert_errors("*[]", &[(1, 2)]);
+    assert_errors("docs/[]*/[a-z]*", &[(5, 2)]);
+}
+
+#[test]
+fn test_nothing_before_operator() {
+    assert_errors("?*.md", &[(0, 1)]);
+    assert_errors("!?x*/**", &[(1, 1)]);
+    assert_errors("docs/+*", &[(5, 1)]);
+}
+
+#[test]
+fn test_offsets_survive_optional_expansion() {
+    // Each variant of the optional `s` finds the bracket, which is only reported once
+    assert_errors("docs?/*[ab", &[(7, 1)]);
+    assert_errors("a?b?/*[", &[(6, 1)]);
+    let glob = Glob::new("x?/*[");
+    assert_eq!(glob.errors()[0].message, "`[` is never closed, so the pattern can't match");
+}
+
+#[test]
+fn test_other_dialects_are_not_checked() {
+    assert!(Glob::with_dialect("?.md", Dialect::Picomatch { dot: false }).errors().is_empty());
+    assert!(Glob::with_dialect("file[ab*", Dialect::Gitignore).errors().is_empty());
+}
@@ -0,0 +1,92 @@
+use pathglob::{github_to_picomatch, picomatch_to_github};
+
+fn assert_to_picomatch(pattern: &str, expected: &str, exact: bool) {
+    let conversion = github_to_picomatch(pattern);
+    assert_eq!(conversion.patterns, vec![expected.to_string()], "GitHub '{}' -> {:?}", pattern, conversion);
+    assert_eq!(conversion.is_exact(), exact, "GitHub '{}' -> {:?}", pattern, conversion.issues);
+}
+
+fn assert_to_github(pattern: &str, expected: &[&str], exact: bool) {
+    let conversion = picomatch_to_github(pattern);
+    assert_eq!(conversion.patterns, expected, "picomatch '{}' -> {:?}", pattern, conversion);
+    assert_eq!(conversion.is_exact(), exact, "picomatch '{}' -> {:?}", pattern, conversion.issues);
+}
+
+#[test]
+fn test_github_to_picomatch_common_patterns() {
+    assert_to_picomatch("*", "*", true);
+    assert_to_picomatch("**", "**", true);
+    assert_to_picomatch("docs/**/*.md", "docs/**/*.md", true);
+    assert_to_picomatch("**/*src/**", "**/*src/**", true);
+    assert_to_picomatch("**.js", "**/*.js", true); // ** followed by a suffix spans directories
+    assert_to_picomatch("[CB]at", "[CB]at", true);
+}
+
+#[test]
+fn test_github_to_picomatch_quantifiers() {
+    assert_to_picomatch("*.jsx?", "{*.js,*.jsx}", true); // ? is optional, not any character
+    assert_to_picomatch("file+.txt", "fil+(e).txt", true);
+    assert_to_picomatch("+file.txt", "file.txt", false); // never matches in GitHub
+}
+
+#[test]
+fn test_github_to_picomatch_escapes_literals() {
+    assert_to_picomatch("{a,b}.txt", "\\{a\\,b\\}.txt", true); // braces are literal in GitHub
+    assert_to_picomatch("[!a]", "[\\!a]", true); // ! is a class member in GitHub
+    assert_to_picomatch("@(x)", "\\@\\(x\\)", true);
+}
+
+#[test]
+fn test_github_to_picomatch_flags_constructs_without_equivalent() {
+    let conversion = github_to_picomatch("!README.md");
+    assert_eq!(conversion.patterns, vec!["!README.md"]);
+    assert_eq!(conversion.issues.len(), 1);
+    assert_eq!(conversion.issues[0].offset, 0);
+
+    let conversion = github_to_picomatch("file[ab.txt");
+    assert_eq!(conversion.issues.len(), 1);
+    assert_eq!(conversion.issues[0].offset, 4);
+
+    assert!(!github_to_picomatch("?abc").is_exact());
+    assert!(!github_to_picomatch("test[].txt").is_exact());
+}
+
+#[test]
+fn test_picomatch_to_github_common_patterns() {
+    assert_to_github("**/*.js", &["**/*.js"], true);
+    assert_to_github("docs/**", &["docs/**"], true);
+    assert_to_github("[a-c]*.md", &["[a-c]*.md"], true);
+    assert_to_github("**.js", &["*.js"], true); // ** inside a segment is a single star
+}
+
+#[test]
+fn test_picomatch_to_github_expands_alternatives() {
+    assert_to_github("src/{app,lib}/*.ts", &["src/app/*.ts", "src/lib/*.ts"], true);
+    assert_to_github("v{1..3}.md", &["v1.md", "v2.md", "v3.md"], true);
+    assert_to_github("*.@(js|ts)", &["*.js", "*.ts"], true);
+    assert_to_github("*.js?(x)", &["*.js", "*.jsx"], true);
+    assert_to_github("fil+(e).txt", &["file+.txt"], true);
+}
+
+#[test]
+fn test_picomatch_to_github_flags_constructs_without_equivalent() {
+    assert_to_github("file?.txt", &["file*.txt"], false); // ? is a single character
+    assert_to_github("[!a]*", &["[a]*"], false);
+    assert_to_github("!(*.md)", &["*"], false);
+    assert_to_github("a\\*b", &["a*b"], false); // GitHub cannot escape *
+                                                // Widening next to a star mustn't make a `**`
+    assert_to_github("*?", &["*"], false);
+    assert_to_github("?*.md", &["*.md"], false);
+    assert_to_github("*+(ab)", &["*"], false);
+    assert_to_github("docs/*!(a)?/x", &["docs/*/x"], false);
+
+    let conversion = picomatch_to_github("docs/?.md");
+    assert_eq!(conversion.issues[0].offset, 5);
+}
+
+#[test]
+fn test_picomatch_to_github_limits_expansion() {
+    assert_to_github("v{1..2000000000}", &["v{1..2000000000}"], true);
+    // Too many alternatives to expand into separate patterns
+    assert_to_github("{1..100}{1..100}{1..100}.md", &["*.md"], false);
+}
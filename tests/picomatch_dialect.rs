@@ -0,0 +1,119 @@
+use pathglob::{Dialect, Glob};
+
+const PICOMATCH: Dialect = Dialect::Picomatch { dot: false };
+const PATHS_FILTER: Dialect = Dialect::Picomatch { dot: true };
+
+fn assert_dialect_match(dialect: Dialect, pattern: &str, path: &str, expected: bool) {
+    let matches = Glob::with_dialect(pattern, dialect).is_match(path);
+    assert_eq!(matches, expected, "Pattern '{}' vs '{}' -> {} (expected {})", pattern, path, matches, expected);
+}
+
+#[test]
+fn test_question_mark_is_any_single_character() {
+    assert_dialect_match(PICOMATCH, "file?.txt", "file1.txt", true);
+    assert_dialect_match(PICOMATCH, "file?.txt", "file.txt", false);
+    assert_dialect_match(PICOMATCH, "file?.txt", "file12.txt", false);
+    assert_dialect_match(PICOMATCH, "a?b", "a/b", false); // ? doesn't match slash
+}
+
+#[test]
+fn test_globstar() {
+    assert_dialect_match(PICOMATCH, "**/*.js", "index.js", true);
+    assert_dialect_match(PICOMATCH, "**/*.js", "src/js/app.js", true);
+    assert_dialect_match(PICOMATCH, "docs/**", "docs/a/b.md", true);
+    assert_dialect_match(PICOMATCH, "**.js", "src/app.js", false); // ** inside a segment is a single star
+    assert_dialect_match(PICOMATCH, "**.js", "app.js", true);
+}
+
+#[test]
+fn test_brace_expansion() {
+    assert_dialect_match(PICOMATCH, "src/{app,lib}/*.ts", "src/app/main.ts", true);
+    assert_dialect_match(PICOMATCH, "src/{app,lib}/*.ts", "src/lib/main.ts", true);
+    assert_dialect_match(PICOMATCH, "src/{app,lib}/*.ts", "src/test/main.ts", false);
+    assert_dialect_match(PICOMATCH, "{src,test/unit}/**", "test/unit/a.rs", true); // alternatives may contain slashes
+    assert_dialect_match(PICOMATCH, "v{1..3}.md", "v2.md", true);
+    assert_dialect_match(PICOMATCH, "v{1..3}.md", "v4.md", false);
+    assert_dialect_match(PICOMATCH, "{a}.md", "{a}.md", true); // a single alternative is literal
+}
+
+#[test]
+fn test_extglobs() {
+    assert_dialect_match(PICOMATCH, "*.@(js|ts)", "app.ts", true);
+    assert_dialect_match(PICOMATCH, "*.@(js|ts)", "app.rs", false);
+    assert_dialect_match(PICOMATCH, "*.js?(x)", "app.jsx", true);
+    assert_dialect_match(PICOMATCH, "*.js?(x)", "app.js", true);
+    assert_dialect_match(PICOMATCH, "a+(b)c", "abbbc", true);
+    assert_dialect_match(PICOMATCH, "a+(b)c", "ac", false);
+    assert_dialect_match(PICOMATCH, "a*(b)c", "ac", true);
+    assert_dialect_match(PICOMATCH, "docs/!(*.md)", "docs/image.png", true);
+    assert_dialect_match(PICOMATCH, "docs/!(*.md)", "docs/README.md", false);
+}
+
+#[test]
+fn test_character_classes() {
+    assert_dialect_match(PICOMATCH, "[a-c].txt", "b.txt", true);
+    assert_dialect_match(PICOMATCH, "[!a-c].txt", "b.txt", false);
+    assert_dialect_match(PICOMATCH, "[^a-c].txt", "d.txt", true);
+    assert_dialect_match(PICOMATCH, "[[:digit:]].txt", "7.txt", true);
+    assert_dialect_match(PICOMATCH, "\\*.txt", "*.txt", true);
+    assert_dialect_match(PICOMATCH, "\\*.txt", "a.txt", false);
+}
+
+#[test]
+fn test_dotfiles() {
+    assert_dialect_match(PICOMATCH, "*", ".env", false);
+    assert_dialect_match(PICOMATCH, ".*", ".env", true);
+    assert_dialect_match(PICOMATCH, "**/*.yml", ".github/workflows/ci.yml", false);
+    assert_dialect_match(PICOMATCH, ".github/**/*.yml", ".github/workflows/ci.yml", true);
+
+    assert_dialect_match(PATHS_FILTER, "*", ".env", true);
+    assert_dialect_match(PATHS_FILTER, "**/*.yml", ".github/workflows/ci.yml", true);
+}
+
+#[test]
+fn test_negation_and_leading_dot_slash() {
+    let glob = Glob::with_dialect("!**/*.md", PICOMATCH);
+    assert!(glob.is_negation());
+    assert!(glob.is_match("docs/README.md"));
+
+    assert_dialect_match(PICOMATCH, "./src/*.rs", "src/lib.rs", true);
+}
+
+#[test]
+fn test_github_dialect_is_default() {
+    assert_eq!(Glob::new("*.jsx?").dialect(), Dialect::GitHub);
+    assert_dialect_match(Dialect::GitHub, "*.jsx?", "app.js", true);
+    assert_dialect_match(PICOMATCH, "*.jsx?", "app.js", false);
+}
+
+#[test]
+fn test_regex_export() {
+    assert_eq!(Glob::with_dialect("src/*.@(js|ts)", PATHS_FILTER).to_regex_string(), "^src/[^/]*\\.(?:js|ts)$");
+    assert_eq!(Glob::with_dialect("a?[!b]", PATHS_FILTER).to_regex_string(), "^a[^/][^/b]$");
+    assert_eq!(Glob::with_dialect("{a,b}/**", PATHS_FILTER).to_regex_string(), "^(?:a(?:/.*)?|b(?:/.*)?)$");
+}
+
+#[test]
+fn test_expansion_limits() {
+    // Ranges past picomatch's rangeLimit of 1000 values are literal
+    assert_dialect_match(PICOMATCH, "v{1..1000}.md", "v1000.md", true);
+    assert_dialect_match(PICOMATCH, "v{1..2000000000}.md", "v5.md", false);
+    assert_dialect_match(PICOMATCH, "v{1..2000000000}.md", "v{1..2000000000}.md", true);
+    assert_dialect_match(PICOMATCH, "v{0..2000000000..10000000}.md", "v30000000.md", true);
+    // Past 10000 variants braces are matched within a segment instead of being expanded
+    let nested = "{1..100}{1..100}{1..100}/{a,b/c}";
+    assert_dialect_match(PICOMATCH, nested, "12345/a", true);
+    assert_dialect_match(PICOMATCH, nested, "1234/b/c", false);
+}
+
+#[test]
+fn test_nesting_limit() {
+    // Groups nested more than 64 deep are literal
+    let nested = |open: &str, close: &str, depth: usize| format!("{}a{}", open.repeat(depth), close.repeat(depth));
+    assert_dialect_match(PICOMATCH, &nested("@(", ")", 64), "a", true);
+    assert_dialect_match(PICOMATCH, &nested("@(", ")", 100), &nested("@(", ")", 36), true);
+    assert_dialect_match(PICOMATCH, &nested("{b,", "}", 64), "a", true);
+    assert_dialect_match(PICOMATCH, &nested("{b,", "}", 100), &nested("{b,", "}", 36), true);
+    assert_dialect_match(PICOMATCH, &nested("@(", ")", 50_000), "a", false);
+    assert_dialect_match(PICOMATCH, &nested("{b,", "}", 50_000), "a", false);
+}
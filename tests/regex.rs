@@ -0,0 +1,127 @@
+use pathglob::{Dialect, Glob};
+
+fn assert_regex(pattern: &str, expected: &str) {
+    let regex = Glob::new(pattern).to_regex_string();
+    assert_eq!(regex, expected, "Pattern '{}' -> '{}' (expected '{}')", pattern, regex, expected);
+}
+
+#[test]
+fn test_wildcards() {
+    assert_regex("*", "^[^/]*$");
+    assert_regex("*.js", "^[^/]*\\.js$");
+    assert_regex("docs/*", "^docs/[^/]*$");
+    assert_regex("Octo*", "^Octo[^/]*$");
+}
+
+#[test]
+fn test_double_star() {
+    assert_regex("**", "^.*$");
+    assert_regex("docs/**", "^docs(?:/.*)?$");
+    assert_regex("**/README.md", "^(?:.*/)?README\\.md$");
+    assert_regex("docs/**/*.md", "^docs(?:/.*)?/[^/]*\\.md$");
+    assert_regex("**/docs/**", "^(?:.*/)?docs(?:/.*)?$");
+    assert_regex("**/*src/**", "^(?:.*/)?[^/]*src(?:/.*)?$");
+}
+
+#[test]
+fn test_double_star_with_suffix() {
+    assert_regex("**.js", "^(?:[^/]*/)*[^/]*\\.js$");
+    assert_regex("src/**.rs", "^src/(?:[^/]*/)*[^/]*\\.rs$");
+}
+
+#[test]
+fn test_quantifiers() {
+    // Optionals are expanded into alternatives, like the matcher does
+    assert_regex("*.jsx?", "^(?:[^/]*\\.js|[^/]*\\.jsx)$");
+    assert_regex("file+.txt", "^file+\\.txt$");
+    assert_regex("*.jsx+", "^[^/]*\\.jsx+$");
+    assert_regex("+file.txt", "^[^\\s\\S]$"); // bogus pattern never matches
+                                              // `+` takes every repetition, so nothing after it can match one more
+    assert_regex("a+a", "^a+(?!a)a$");
+    assert_regex("a+*", "^a+(?!a)[^/]*$");
+    assert_regex("*+*", "^[^/]*\\*+(?!\\*)[^/]*$");
+    assert!(!Glob::new("a+a").is_match("aa"));
+    assert!(!Glob::new("a+a").is_match("aaa"));
+}
+
+#[test]
+fn test_brackets() {
+    assert_regex("[CB]at", "^[CB]at$");
+    assert_regex("[1-2]00", "^[1-2]00$");
+    assert_regex("[0-9a-zA-Z]", "^[0-9a-zA-Z]$");
+    assert_regex("test[].txt", "^test[^\\s\\S]\\.txt$");
+    assert_regex("file[ab.txt", "^[^\\s\\S]$"); // unclosed bracket never matches
+}
+
+#[test]
+fn test_negation_is_not_part_of_regex() {
+    let glob = Glob::new("!README.md");
+    assert!(glob.is_negation());
+    assert_eq!(glob.to_regex_string(), "^README\\.md$");
+}
+
+#[test]
+fn test_literal_characters_are_escaped() {
+    assert_regex("a.b(c)|d{e}$^", "^a\\.b\\(c\\)\\|d\\{e\\}\\$\\^$");
+}
+
+// Whether a regex character class body like "a-z\\x7f" contains `c`
+fn regex_class_contains(class: &str, c: char) -> bool {
+    let mut members = Vec::new();
+    let mut chars = class.chars();
+    while let Some(member) = chars.next() {
+        members.push(match member {
+            '\\' => match chars.next().unwrap() {
+                'x' => char::from(u8::from_str_radix(&chars.by_ref().take(2).collect::<String>(), 16).unwrap()),
+                't' => '\t',
+                'n' => '\n',
+                'r' => '\r',
+                'f' => '\x0c',
+                'v' => '\x0b',
+                escaped => escaped,
+            },
+            member => member,
+        });
+    }
+
+    let mut idx = 0;
+    while idx < members.len() {
+        if members.get(idx + 1) == Some(&'-') && idx + 2 < members.len() {
+            if members[idx] <= c && c <= members[idx + 2] {
+                return true;
+            }
+            idx += 3;
+        } else if members[idx] == c {
+            return true;
+        } else {
+            idx += 1;
+        }
+    }
+    false
+}
+
+#[test]
+fn test_posix_classes_agree_with_matcher() {
+    let dialect = Dialect::Picomatch { dot: true };
+    let names = [
+        "alnum", "alpha", "blank", "cntrl", "digit", "graph", "lower", "print", "punct", "space", "upper", "word",
+        "xdigit",
+    ];
+
+    for name in names {
+        let glob = Glob::with_dialect(&format!("[[:{}:]]", name), dialect);
+        let regex = glob.to_regex_string();
+        let class = regex.strip_prefix("^[").and_then(|rest| rest.strip_suffix("]$")).unwrap();
+        for c in (0..128u8).map(char::from).filter(|c| *c != '/') {
+            assert_eq!(
+                regex_class_contains(class, c),
+                glob.is_match(&c.to_string()),
+                "[:{}:] disagrees on {:?} with {}",
+                name,
+                c,
+                regex
+            );
+        }
+    }
+    assert!(Glob::with_dialect("[[:punct:]]", dialect).is_match("!"));
+}
@@ -0,0 +1,91 @@
+use std::io::Cursor;
+
+use pathglob::{run_repl, Dialect};
+
+const FILES: &[&str] = &["README.md", "docs/Guide.md", "src/a.rs", "src/b.md", "src/sub/c.rs"];
+
+// Runs a session with the lines as input and returns what it printed after the help
+fn run(lines: &[&str], dialect: Dialect) -> String {
+    let files: Vec<String> = FILES.iter().map(|file| file.to_string()).collect();
+    let input: String = lines.iter().map(|line| format!("{}\n", line)).collect();
+    let mut out = Vec::new();
+    run_repl(&mut Cursor::new(input), &mut out, &files, dialect, false).unwrap();
+
+    let out = String::from_utf8(out).unwrap();
+    out.split_once("leave, like the end of input\n").unwrap().1.to_string()
+}
+
+#[test]
+fn test_matches_and_near_misses() {
+    assert_eq!(
+        run(&["src/*", "!**.md"], Dialect::GitHub),
+        "  0 src/*
+2 of 5 files included
+  [1] src/a.rs
+  [2] src/b.md
+near misses:
+  [3] src/sub/c.rs: too deep for pattern 0 `src/*`, `src/**` would include it
+  0 src/*
+  1 !**.md
+1 of 5 files included
+  [1] src/a.rs
+near misses:
+  [2] src/b.md: excluded by pattern 1 `!**.md`
+  [3] src/sub/c.rs: too deep for pattern 0 `src/*`, `src/**` would include it
+"
+    );
+}
+
+#[test]
+fn test_case_and_typo_near_misses() {
+    assert_eq!(
+        run(&["readme.md", ":clear", "doc/*.md"], Dialect::GitHub),
+        "  0 readme.md
+0 of 5 files included
+near misses:
+  [1] README.md: differs in case from pattern 0 `readme.md`
+no patterns yet
+0 of 5 files included
+  0 doc/*.md
+0 of 5 files included
+near misses:
+  [1] docs/Guide.md: pattern 0 `doc/*.md` would include it as `docs/*.md`
+"
+    );
+
+    // Typos are only corrected in GitHub patterns
+    assert!(!run(&["doc/*.md"], Dialect::Gitignore).contains("near misses"));
+}
+
+#[test]
+fn test_explain() {
+    let out = run(&["src/**", "!**.md", ":explain 3", ":explain README.md", ":explain 9"], Dialect::GitHub);
+    assert!(out.ends_with(
+        "src/b.md: excluded by pattern 1 `!**.md`
+  0 src/**: matches, includes
+  1 !**.md: matches, excludes  <- decides
+README.md: excluded by pattern 1 `!**.md`
+  0 src/**: no match
+  1 !**.md: matches, excludes  <- decides
+no file numbered 9
+"
+    ));
+}
+
+#[test]
+fn test_commands() {
+    assert_eq!(
+        run(&["file[ab*", ":list", ":pop", ":list", ":nope", ":quit", "src/**"], Dialect::GitHub),
+        "error: `[` is never closed, so the pattern can't match
+    file[ab*
+        ^
+  0 file[ab*
+0 of 5 files included
+  0 file[ab*
+no patterns yet
+0 of 5 files included
+no patterns yet
+unknown command :nope, see :help
+"
+    );
+}
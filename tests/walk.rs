@@ -0,0 +1,64 @@
+use pathglob::{find_files, find_files_bytes, Filter};
+
+mod common;
+use common::TempDir;
+
+const TREE: &[&str] = &[
+    "README.md",
+    "docs/index.md",
+    "docs/guide/install.md",
+    "docs/images/logo.png",
+    "src/main.rs",
+    "src/docs/notes.md",
+    ".github/workflows/ci.yml",
+    ".git/config",
+];
+
+#[test]
+fn test_find_files() {
+    let root = TempDir::with_files("walk-find", TREE);
+
+    let files = find_files(&root, &Filter::new(&["docs/**"])).unwrap();
+    assert_eq!(files, ["docs/guide/install.md", "docs/images/logo.png", "docs/index.md"]);
+
+    let files = find_files(&root, &Filter::new(&["**.md", "!docs/guide/**"])).unwrap();
+    assert_eq!(files, ["README.md", "docs/index.md", "src/docs/notes.md"]);
+
+    let files = find_files(&root, &Filter::new(&[".github/workflows/*.yml"])).unwrap();
+    assert_eq!(files, [".github/workflows/ci.yml"]);
+}
+
+#[test]
+fn test_git_directory_is_skipped() {
+    let root = TempDir::with_files("walk-git", TREE);
+
+    let files = find_files(&root, &Filter::new(&["**"])).unwrap();
+    assert_eq!(files.len(), 7);
+    assert!(!files.iter().any(|file| file.starts_with(".git/")));
+}
+
+#[test]
+fn test_patterns_matching_nothing() {
+    let root = TempDir::with_files("walk-nothing", TREE);
+
+    assert!(find_files(&root, &Filter::new(&["srcs/**"])).unwrap().is_empty());
+    assert!(find_files(&root, &Filter::new(&["!docs/**"])).unwrap().is_empty());
+    assert!(find_files(&root, &Filter::new(&[])).unwrap().is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_names_that_are_not_utf8() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::Path;
+
+    let root = TempDir::with_files("walk-bytes", &["docs/index.md"]);
+    root.write(Path::new("docs").join(OsStr::from_bytes(b"\xe2\x82.md")), "");
+
+    // The name is matched as it is, not as the U+FFFD a lossy conversion would give
+    let files = find_files_bytes(&root, &Filter::new(&["docs/*.md"])).unwrap();
+    assert_eq!(files, [b"docs/index.md".as_slice(), b"docs/\xe2\x82.md"]);
+    assert!(find_files_bytes(&root, &Filter::new(&["docs/\u{fffd}.md"])).unwrap().is_empty());
+    assert_eq!(find_files(&root, &Filter::new(&["docs/*"])).unwrap(), ["docs/index.md", "docs/\u{fffd}.md"]);
+}
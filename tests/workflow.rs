@@ -0,0 +1,129 @@
+use pathglob::parse_workflow;
+
+const WORKFLOW: &str = r#"name: Release
+on:
+  push:
+    branches:
+      - main
+      - 'release/**'
+    tags: ['v*']
+    paths:
+      - 'src/**'
+      - '!src/**/*.md'
+  pull_request:
+    paths-ignore:
+      - "docs/**"
+  workflow_dispatch:
+
+jobs:
+  build:
+    runs-on: ubuntu-latest
+"#;
+
+#[test]
+fn test_parse_workflow() {
+    let workflow = parse_workflow(WORKFLOW);
+    assert_eq!(workflow.name.as_deref(), Some("Release"));
+
+    let names: Vec<&str> = workflow.events.iter().map(|event| event.name.as_str()).collect();
+    assert_eq!(names, ["push", "pull_request", "workflow_dispatch"]);
+
+    let push = workflow.event("push").unwrap();
+    let paths: Vec<(&str, usize, usize)> = push
+        .paths
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|pattern| (pattern.glob.pattern(), pattern.line, pattern.column))
+        .collect();
+    assert_eq!(paths, [("src/**", 9, 9), ("!src/**/*.md", 10, 9)]);
+    assert_eq!(push.tags.as_ref().unwrap()[0].glob.pattern(), "v*");
+    assert!(push.paths_ignore.is_none());
+
+    let dispatch = workflow.event("workflow_dispatch").unwrap();
+    assert!(dispatch.paths.is_none() && dispatch.branches.is_none());
+}
+
+#[test]
+fn test_event_lists() {
+    let names =
+        |content: &str| -> Vec<String> { parse_workflow(content).events.into_iter().map(|event| event.name).collect() };
+
+    assert_eq!(names("on: push\n"), ["push"]);
+    assert_eq!(names("on: [push, pull_request]\n"), ["push", "pull_request"]);
+    assert_eq!(names("on:\n  - push\n  - pull_request\n"), ["push", "pull_request"]);
+    assert!(names("jobs: {}\n").is_empty());
+}
+
+#[test]
+fn test_is_triggered_by() {
+    let workflow = parse_workflow(WORKFLOW);
+
+    let push = workflow.event("push").unwrap();
+    assert!(push.is_triggered_by(&["src/main.rs"]));
+    assert!(push.is_triggered_by(&["src/README.md", "src/lib.rs"]));
+    assert!(!push.is_triggered_by(&["src/README.md"])); // excluded by the negation
+    assert!(!push.is_triggered_by(&["docs/index.md"]));
+
+    let pull_request = workflow.event("pull_request").unwrap();
+    assert!(pull_request.is_triggered_by(&["src/main.rs"]));
+    assert!(pull_request.is_triggered_by(&["docs/index.md", "Cargo.toml"]));
+    assert!(!pull_request.is_triggered_by(&["docs/index.md"]));
+
+    let dispatch = workflow.event("workflow_dispatch").unwrap();
+    assert!(dispatch.is_triggered_by(&["anything"]));
+}
+
+#[test]
+fn test_runs_for_branch() {
+    let workflow = parse_workflow(WORKFLOW);
+
+    let push = workflow.event("push").unwrap();
+    assert!(push.runs_for_branch("main"));
+    assert!(push.runs_for_branch("release/1.x"));
+    assert!(!push.runs_for_branch("feature/x"));
+    assert!(workflow.event("pull_request").unwrap().runs_for_branch("feature/x"));
+
+    let tags_only = parse_workflow("on:\n  push:\n    tags: ['v*']\n");
+    assert!(!tags_only.event("push").unwrap().runs_for_branch("main"));
+
+    let ignored = parse_workflow("on:\n  push:\n    branches-ignore: ['gh-pages']\n");
+    assert!(!ignored.event("push").unwrap().runs_for_branch("gh-pages"));
+    assert!(ignored.event("push").unwrap().runs_for_branch("main"));
+}
+
+// Runs the parser on a thread, so that a parser stuck on malformed input fails the test instead of hanging it
+fn parse_within_time_limit<T: Send + 'static>(parse: impl FnOnce() -> T + Send + 'static) -> T {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || sender.send(parse()));
+    receiver.recv_timeout(std::time::Duration::from_secs(5)).expect("parsing didn't finish")
+}
+
+#[test]
+fn test_mismatched_flow_brackets() {
+    let workflow = parse_within_time_limit(|| parse_workflow("on:\n  push:\n    paths: [a}\n"));
+    let paths = workflow.event("push").unwrap().paths.as_ref().unwrap();
+    assert_eq!(paths.iter().map(|pattern| pattern.glob.pattern()).collect::<Vec<_>>(), ["a"]);
+
+    let workflow = parse_within_time_limit(|| parse_workflow("on: {push: {a], pull_request: {}}\n"));
+    assert!(workflow.event("push").is_some());
+
+    for content in ["on: [push}}]]\n", "on:\n  push:\n    paths: [[}, {]\n      , x]\n"] {
+        parse_within_time_limit(move || parse_workflow(content));
+    }
+
+    let changes = parse_within_time_limit(|| pathglob::parse_gitlab_ci("job:\n  rules:\n    - changes: [a}\n"));
+    assert_eq!(changes.len(), 1);
+}
+
+#[test]
+fn test_deeply_nested_flow_collections() {
+    let content = format!("on:\n  push:\n    paths: [{}, a]\n", "[".repeat(100_000));
+    let workflow = parse_within_time_limit(move || parse_workflow(&content));
+    assert!(workflow.event("push").is_some());
+
+    let content = format!("on: {{push: {{paths: [{}a{}, b]}}}}\n", "[".repeat(200), "]".repeat(200));
+    let workflow = parse_within_time_limit(move || parse_workflow(&content));
+    let paths = workflow.event("push").unwrap().paths.as_ref().unwrap();
+    assert_eq!(paths.iter().map(|pattern| pattern.glob.pattern()).collect::<Vec<_>>(), ["b"]);
+}